@@ -0,0 +1,170 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use rayon::prelude::*;
+use walkdir::WalkDir;
+use crate::error::ForestDataError;
+use crate::forest_property_data::ForestPropertyData;
+use crate::writer::XmlWriteOptions;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BatchFormat {
+    #[default]
+    Json,
+    GeoJson,
+    Xml,
+}
+
+impl BatchFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            BatchFormat::Json => "json",
+            BatchFormat::GeoJson => "geojson",
+            BatchFormat::Xml => "xml",
+        }
+    }
+}
+
+// Options for converting every XML file in a directory tree
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    pub format: BatchFormat,
+    // Root of a tree mirroring the input directory, or `None` to write next to
+    // the inputs. Required for `Xml`.
+    pub output_dir: Option<PathBuf>,
+    // Indented JSON output
+    pub pretty: bool,
+    // Used when `format` is `Xml`
+    pub xml: XmlWriteOptions,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        BatchOptions {
+            format: BatchFormat::Json,
+            output_dir: None,
+            pretty: true,
+            xml: XmlWriteOptions::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchSuccess {
+    pub input: PathBuf,
+    pub output: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchFailure {
+    pub input: PathBuf,
+    pub message: String,
+}
+
+// Outcome of a batch conversion, in input path order
+#[derive(Debug, Clone, Default)]
+pub struct BatchReport {
+    pub succeeded: Vec<BatchSuccess>,
+    pub failed: Vec<BatchFailure>,
+}
+
+impl BatchReport {
+    pub fn total(&self) -> usize {
+        self.succeeded.len() + self.failed.len()
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+impl fmt::Display for BatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Converted {} of {} files, {} failed", self.succeeded.len(), self.total(), self.failed.len())?;
+        for failure in &self.failed {
+            writeln!(f, "  {}: {}", failure.input.display(), failure.message)?;
+        }
+        Ok(())
+    }
+}
+
+// Finds the XML files under `dir`, sorted by path
+pub fn find_xml_files(dir: &Path) -> Result<Vec<PathBuf>, ForestDataError> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(dir) {
+        let entry = entry.map_err(|e| ForestDataError::Io(e.into()))?;
+        let is_xml = entry.path().extension().is_some_and(|ext| ext.eq_ignore_ascii_case("xml"));
+        if entry.file_type().is_file() && is_xml {
+            files.push(entry.into_path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+// Converts every XML file under `input_dir` in parallel. A file that fails is
+// recorded in the report and does not stop the others.
+pub fn convert_directory(input_dir: &Path, opts: &BatchOptions) -> Result<BatchReport, ForestDataError> {
+    let files = find_xml_files(input_dir)?;
+
+    // XML written next to the inputs would replace them
+    let same_dir = |dir: &Path| fs::canonicalize(dir).ok() == fs::canonicalize(input_dir).ok();
+    if opts.format == BatchFormat::Xml && opts.output_dir.as_deref().is_none_or(same_dir) {
+        return Err(ForestDataError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "XML output needs an output directory other than the input directory",
+        )));
+    }
+
+    let results: Vec<_> = files.par_iter()
+        .map(|input| {
+            let output = output_path(input_dir, input, opts);
+            convert_file(input, &output, opts)
+                .map(|_| BatchSuccess { input: input.clone(), output })
+                .map_err(|e| BatchFailure { input: input.clone(), message: e.to_string() })
+        })
+        .collect();
+
+    let mut report = BatchReport::default();
+    for result in results {
+        match result {
+            Ok(success) => report.succeeded.push(success),
+            Err(failure) => report.failed.push(failure),
+        }
+    }
+    Ok(report)
+}
+
+fn output_path(input_dir: &Path, input: &Path, opts: &BatchOptions) -> PathBuf {
+    let path = match &opts.output_dir {
+        Some(output_dir) => output_dir.join(input.strip_prefix(input_dir).unwrap_or(input)),
+        None => input.to_path_buf(),
+    };
+    path.with_extension(opts.format.extension())
+}
+
+pub fn convert_file(input: &Path, output: &Path, opts: &BatchOptions) -> Result<(), ForestDataError> {
+    if input == output {
+        return Err(ForestDataError::Io(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            "output would overwrite the input file",
+        )));
+    }
+
+    let property = ForestPropertyData::try_from_xml_file(&input.to_string_lossy())?;
+
+    let data = match opts.format {
+        BatchFormat::Json if opts.pretty => serde_json::to_vec_pretty(&property),
+        BatchFormat::Json => serde_json::to_vec(&property),
+        BatchFormat::GeoJson if opts.pretty => serde_json::to_vec_pretty(&property.to_geojson()?),
+        BatchFormat::GeoJson => serde_json::to_vec(&property.to_geojson()?),
+        BatchFormat::Xml => Ok(property.to_xml(&opts.xml)?),
+    }
+    .map_err(|e| ForestDataError::Serialization(e.to_string()))?;
+
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(output, data)?;
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use chrono::NaiveDate;
+use crate::codes::{TreeSpecies, TreeStandDataType};
+use crate::forest_property_data::{ForestPropertyData, StStand, TsTreeStandDataDate, TssTreeStandSummary, TstTreeStratum};
+use crate::values::Typed;
+
+// Factors for converting biomass to carbon and for estimating missing biomass
+#[derive(Debug, Clone, PartialEq)]
+pub struct CarbonFactors {
+    // Carbon content of dry biomass, t C / t
+    pub carbon_fraction: f64,
+    // t CO2 / t C
+    pub co2_per_carbon: f64,
+    // Dry biomass of leaves, branches, stem and stump per m³ of stem volume,
+    // t / m³, used for strata and summaries that have volume but no biomass
+    pub expansion_factors: Vec<(TreeSpecies, f64)>,
+    // For species not in `expansion_factors`
+    pub default_expansion_factor: f64,
+}
+
+impl Default for CarbonFactors {
+    // The expansion factors are the ratios of biomass to volume in
+    // Metsäkeskus exports that carry both
+    fn default() -> Self {
+        CarbonFactors {
+            carbon_fraction: 0.5,
+            co2_per_carbon: 44.0 / 12.0,
+            expansion_factors: vec![
+                (TreeSpecies::ScotsPine, 0.66),
+                (TreeSpecies::NorwaySpruce, 0.76),
+                (TreeSpecies::SilverBirch, 0.77),
+                (TreeSpecies::DownyBirch, 0.75),
+                (TreeSpecies::Aspen, 0.73),
+            ],
+            default_expansion_factor: 0.75,
+        }
+    }
+}
+
+impl CarbonFactors {
+    pub fn expansion_factor(&self, species: &TreeSpecies) -> f64 {
+        self.expansion_factors.iter()
+            .find(|(s, _)| s == species)
+            .map_or(self.default_expansion_factor, |(_, factor)| *factor)
+    }
+}
+
+// Biomass and carbon of a stand or a group of stands, in tonnes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CarbonStock {
+    pub area_ha: f64,
+    // Dry biomass of leaves, branches, stems and stumps
+    pub biomass: f64,
+    // The part of `biomass` estimated from volume
+    pub estimated_biomass: f64,
+    pub carbon: f64,
+    pub co2: f64,
+}
+
+impl CarbonStock {
+    fn new(area_ha: f64, biomass_per_ha: f64, estimated_per_ha: f64, factors: &CarbonFactors) -> CarbonStock {
+        let biomass = biomass_per_ha * area_ha;
+        let carbon = biomass * factors.carbon_fraction;
+        CarbonStock {
+            area_ha,
+            biomass,
+            estimated_biomass: estimated_per_ha * area_ha,
+            carbon,
+            co2: carbon * factors.co2_per_carbon,
+        }
+    }
+
+    pub fn add(&mut self, other: &CarbonStock) {
+        self.area_ha += other.area_ha;
+        self.biomass += other.biomass;
+        self.estimated_biomass += other.estimated_biomass;
+        self.carbon += other.carbon;
+        self.co2 += other.co2;
+    }
+}
+
+// The carbon stock of a stand at one TreeStandDataDate
+#[derive(Debug, Clone, PartialEq)]
+pub struct CarbonPoint {
+    pub date: NaiveDate,
+    pub data_type: TreeStandDataType,
+    pub stock: CarbonStock,
+    // Change in t CO2 since the previous point, `None` for the first one
+    pub co2_change: Option<f64>,
+}
+
+impl StStand {
+    // The carbon stock of the latest measured or updated tree stand data,
+    // an empty stock over the stand area if the stand has none
+    pub fn carbon_stock(&self, factors: &CarbonFactors) -> CarbonStock {
+        match self.current_tree_stand_data() {
+            Some(date) => self.carbon_stock_at(date, factors),
+            None => CarbonStock { area_ha: self.st_stand_basic_data.st_area.get(), ..CarbonStock::default() },
+        }
+    }
+
+    // The carbon stock at every TreeStandDataDate of the given types, or of
+    // all types if `types` is empty, ordered by date
+    pub fn carbon_history(&self, factors: &CarbonFactors, types: &[TreeStandDataType]) -> Vec<CarbonPoint> {
+        let mut dates: Vec<_> = self.ts_tree_stand_data.iter()
+            .flat_map(|data| &data.ts_tree_stand_data_date)
+            .filter(|date| types.is_empty() || types.contains(&date.ts_tree_stand_data_date_type))
+            .collect();
+        dates.sort_by_key(|date| date.date.get());
+
+        let mut points: Vec<CarbonPoint> = Vec::with_capacity(dates.len());
+        for date in dates {
+            let stock = self.carbon_stock_at(date, factors);
+            let co2_change = points.last().map(|previous| stock.co2 - previous.stock.co2);
+            points.push(CarbonPoint {
+                date: date.date.get(),
+                data_type: date.ts_tree_stand_data_date_type.clone(),
+                stock,
+                co2_change,
+            });
+        }
+        points
+    }
+
+    // Biomass is taken from the strata, or from the summary when the date has
+    // no strata. A stratum or summary without biomass is estimated from its volume.
+    fn carbon_stock_at(&self, date: &TsTreeStandDataDate, factors: &CarbonFactors) -> CarbonStock {
+        let area_ha = self.st_stand_basic_data.st_area.get();
+        let strata = date.tree_strata();
+
+        let (biomass, estimated) = if !strata.is_empty() {
+            strata.iter()
+                .map(|stratum| stratum_biomass(stratum, factors))
+                .fold((0.0, 0.0), |(biomass, estimated), (b, e)| (biomass + b, estimated + e))
+        } else if let Some(summary) = &date.tss_tree_stand_summary {
+            let species = summary.tss_main_tree_species.as_ref().or(self.st_stand_basic_data.st_main_tree_species.as_ref());
+            summary_biomass(summary, species, factors)
+        } else {
+            (0.0, 0.0)
+        };
+
+        CarbonStock::new(area_ha, biomass, estimated, factors)
+    }
+}
+
+// Biomass per hectare and the part of it that was estimated
+fn stratum_biomass(stratum: &TstTreeStratum, factors: &CarbonFactors) -> (f64, f64) {
+    let components = [&stratum.tst_leaf_biomass, &stratum.tst_branch_biomass, &stratum.tst_stem_biomass, &stratum.tst_stump_biomass];
+    match biomass_of(components) {
+        Some(biomass) => (biomass, 0.0),
+        None => {
+            let estimated = value(&stratum.tst_volume).unwrap_or(0.0) * factors.expansion_factor(&stratum.tst_tree_species);
+            (estimated, estimated)
+        }
+    }
+}
+
+fn summary_biomass(summary: &TssTreeStandSummary, species: Option<&TreeSpecies>, factors: &CarbonFactors) -> (f64, f64) {
+    let components = [&summary.tss_leaf_biomass, &summary.tss_branch_biomass, &summary.tss_stem_biomass, &summary.tss_stump_biomass];
+    match biomass_of(components) {
+        Some(biomass) => (biomass, 0.0),
+        None => {
+            let factor = species.map_or(factors.default_expansion_factor, |species| factors.expansion_factor(species));
+            let estimated = summary.tss_volume.get() * factor;
+            (estimated, estimated)
+        }
+    }
+}
+
+fn biomass_of(components: [&Option<Typed<f64>>; 4]) -> Option<f64> {
+    components.into_iter().filter_map(value).reduce(|a, b| a + b)
+}
+
+fn value(field: &Option<Typed<f64>>) -> Option<f64> {
+    field.as_ref().map(|value| value.get())
+}
+
+impl ForestPropertyData {
+    // The current carbon stock of every stand in the document
+    pub fn carbon_stock(&self, factors: &CarbonFactors) -> CarbonStock {
+        let mut total = CarbonStock::default();
+        for stand in self.stands() {
+            total.add(&stand.carbon_stock(factors));
+        }
+        total
+    }
+}
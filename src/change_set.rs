@@ -0,0 +1,577 @@
+use std::collections::{HashMap, HashSet};
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use serde_json::Value;
+use crate::codes::ChangeState;
+use crate::error::ForestDataError;
+use crate::forest_property_data::{
+    DtsDeadTreeStrata, DtsDeadTreeStratum, ForestPropertyData, OpAssortment, OpOperation, OpOperations, ReParcel,
+    ReRealEstate, ReRealEstates, StSpecialFeature, StSpecialFeatures, StStand, StStands, TsTreeStandData, TsTreeStandDataDate,
+    TssTreeStandSummary, TstTreeStrata, TstTreeStratum,
+};
+use crate::values::{with_canonical_text, Typed};
+
+// An element with its own ChangeState in a change-set: strata, tree stand
+// summaries, operations, assortments and special features. Stands carry
+// theirs in StandBasicData.
+trait Tracked: Clone + Serialize {
+    fn id(&self) -> &str;
+    fn change_state(&self) -> Option<&ChangeState>;
+    // Sets the state, and the ChangeTime when `time` is given
+    fn set_change(&mut self, state: ChangeState, time: Option<&Typed<NaiveDateTime>>);
+
+    // Sets the state of the element and of the tracked elements inside it
+    fn set_change_deep(&mut self, state: ChangeState, time: &Typed<NaiveDateTime>) {
+        self.set_change(state, Some(time));
+    }
+
+    // Marks the tracked elements inside the element against `original`
+    fn mark_nested(&mut self, _original: &Self, _time: &Typed<NaiveDateTime>) -> Result<(), ForestDataError> {
+        Ok(())
+    }
+
+    // Replaces the tracked elements inside the element with those of `base`
+    // with the element's own changes applied
+    fn apply_nested(&mut self, _base: Option<&Self>) {}
+}
+
+macro_rules! tracked_fields {
+    () => {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn change_state(&self) -> Option<&ChangeState> {
+            self.co_change_state.as_ref()
+        }
+
+        fn set_change(&mut self, state: ChangeState, time: Option<&Typed<NaiveDateTime>>) {
+            self.co_change_state = Some(state);
+            if let Some(time) = time {
+                self.co_change_time = Some(time.clone());
+            }
+        }
+    };
+}
+
+impl Tracked for TstTreeStratum {
+    tracked_fields!();
+}
+
+impl Tracked for DtsDeadTreeStratum {
+    tracked_fields!();
+}
+
+impl Tracked for TssTreeStandSummary {
+    tracked_fields!();
+}
+
+impl Tracked for OpAssortment {
+    tracked_fields!();
+}
+
+impl Tracked for StSpecialFeature {
+    tracked_fields!();
+}
+
+impl Tracked for OpOperation {
+    tracked_fields!();
+
+    fn set_change_deep(&mut self, state: ChangeState, time: &Typed<NaiveDateTime>) {
+        self.set_change(state.clone(), Some(time));
+        for assortment in assortments_mut(self) {
+            assortment.set_change(state.clone(), Some(time));
+        }
+    }
+
+    fn mark_nested(&mut self, original: &Self, time: &Typed<NaiveDateTime>) -> Result<(), ForestDataError> {
+        if let Some(assortments) = self.op_cutting.as_mut().and_then(|cutting| cutting.op_assortments.as_mut()) {
+            mark_entries(assortments_of(original), &mut assortments.op_assortment, time)?;
+        }
+        Ok(())
+    }
+
+    fn apply_nested(&mut self, base: Option<&Self>) {
+        let base_assortments = base.map(assortments_of).unwrap_or_default();
+        if let Some(assortments) = self.op_cutting.as_mut().and_then(|cutting| cutting.op_assortments.as_mut()) {
+            assortments.op_assortment = apply_entries(base_assortments, &assortments.op_assortment);
+        }
+    }
+}
+
+fn assortments_of(operation: &OpOperation) -> &[OpAssortment] {
+    operation.op_cutting.as_ref()
+        .and_then(|cutting| cutting.op_assortments.as_ref())
+        .map(|assortments| assortments.op_assortment.as_slice())
+        .unwrap_or_default()
+}
+
+fn assortments_mut(operation: &mut OpOperation) -> &mut [OpAssortment] {
+    operation.op_cutting.as_mut()
+        .and_then(|cutting| cutting.op_assortments.as_mut())
+        .map(|assortments| assortments.op_assortment.as_mut_slice())
+        .unwrap_or_default()
+}
+
+impl ForestPropertyData {
+    // Builds a change-set from this document to `edited`: the edited document
+    // reduced to the stands that changed, with ChangeState set on the stand
+    // basic data and on every stratum, operation, assortment and special
+    // feature, and `time` as the ChangeTime of everything inserted, updated
+    // or deleted. Deleted elements are included with ChangeState 3. Stands
+    // and their contents are matched by id.
+    pub fn change_set(&self, edited: &ForestPropertyData, time: NaiveDateTime) -> Result<ForestPropertyData, ForestDataError> {
+        let time = Typed::new(time);
+        let mut changes = edited.clone();
+
+        let mut before_stands: HashMap<&str, &StStand> = HashMap::new();
+        for stand in self.stands() {
+            before_stands.entry(stand.id.as_str()).or_insert(stand);
+        }
+        let edited_ids: HashSet<&str> = edited.stands().map(|stand| stand.id.as_str()).collect();
+
+        let mut mark = |stand: &mut StStand| -> Result<bool, ForestDataError> {
+            match before_stands.get(stand.id.as_str()) {
+                Some(before) => mark_stand(before, stand, &time),
+                None => {
+                    set_stand_deep(stand, ChangeState::Inserted, &time);
+                    Ok(true)
+                }
+            }
+        };
+
+        if let Some(stands) = changes.st_stands.as_mut() {
+            retain_changed(&mut stands.st_stand, &mut mark)?;
+        }
+        for estate in changes.re_real_estates.iter_mut().flat_map(|estates| estates.re_real_estate.iter_mut()) {
+            for parcel in estate.re_parcels.re_parcel.iter_mut() {
+                retain_changed(&mut parcel.st_stands.st_stand, &mut mark)?;
+            }
+        }
+
+        let is_deleted = |stand: &&StStand| !edited_ids.contains(stand.id.as_str());
+        let deleted = |stand: &StStand| {
+            let mut stand = stand.clone();
+            set_stand_deep(&mut stand, ChangeState::Deleted, &time);
+            stand
+        };
+        for stand in self.st_stands.iter().flat_map(|stands| &stands.st_stand).filter(is_deleted) {
+            changes.st_stands.get_or_insert_with(empty_stands).st_stand.push(deleted(stand));
+        }
+        for estate in self.real_estates() {
+            for parcel in &estate.re_parcels.re_parcel {
+                for stand in parcel.st_stands.st_stand.iter().filter(is_deleted) {
+                    parcel_in(&mut changes, estate, parcel).st_stands.st_stand.push(deleted(stand));
+                }
+            }
+        }
+
+        remove_empty(&mut changes);
+        Ok(changes)
+    }
+
+    // Applies a change-set received for this document. Stands with ChangeState
+    // 3 are removed and stands with ChangeState 1 are added, or replaced if
+    // they already exist. For other stands the basic data is replaced and
+    // strata, operations, assortments and special features are inserted,
+    // replaced or removed by their own ChangeState. A stand that is not
+    // inserted must already be in this document; otherwise nothing is applied.
+    pub fn apply_change_set(&mut self, change_set: &ForestPropertyData) -> Result<(), ForestDataError> {
+        let ids: HashSet<&str> = self.stands().map(|stand| stand.id.as_str()).collect();
+        for change in change_set.stands() {
+            let state = change.st_stand_basic_data.co_change_state.as_ref();
+            let inserted_or_deleted = matches!(state, Some(ChangeState::Inserted | ChangeState::Deleted));
+            if !inserted_or_deleted && !ids.contains(change.id.as_str()) {
+                return Err(missing_stand(&change.id));
+            }
+        }
+
+        let mut applied = self.clone();
+        applied.apply_changes(change_set)?;
+        *self = applied;
+        Ok(())
+    }
+
+    fn apply_changes(&mut self, change_set: &ForestPropertyData) -> Result<(), ForestDataError> {
+        let root_stands = change_set.st_stands.iter().flat_map(|stands| &stands.st_stand).map(|stand| (None, stand));
+        let parcel_stands = change_set.real_estates().flat_map(|estate| {
+            estate.re_parcels.re_parcel.iter().flat_map(move |parcel| {
+                parcel.st_stands.st_stand.iter().map(move |stand| (Some((estate, parcel)), stand))
+            })
+        });
+
+        for (location, change) in root_stands.chain(parcel_stands) {
+            let state = change.st_stand_basic_data.co_change_state.as_ref();
+            if state == Some(&ChangeState::Deleted) {
+                remove_stand(self, &change.id);
+                continue;
+            }
+
+            if let Some(existing) = self.stands_mut().find(|stand| stand.id == change.id) {
+                *existing = applied_stand(change, Some(existing));
+                continue;
+            }
+            if state != Some(&ChangeState::Inserted) {
+                return Err(missing_stand(&change.id));
+            }
+
+            let stand = applied_stand(change, None);
+            match location {
+                Some((estate, parcel)) => parcel_in(self, estate, parcel).st_stands.st_stand.push(stand),
+                None => self.st_stands.get_or_insert_with(empty_stands).st_stand.push(stand),
+            }
+        }
+
+        remove_empty(self);
+        Ok(())
+    }
+}
+
+fn missing_stand(id: &str) -> ForestDataError {
+    ForestDataError::ChangeSet(format!("Stand {} is changed but not in the document", id))
+}
+
+// Marks an edited stand against its original. Returns false if nothing changed.
+fn mark_stand(before: &StStand, stand: &mut StStand, time: &Typed<NaiveDateTime>) -> Result<bool, ForestDataError> {
+    let mut changed = false;
+
+    let basic_data = &mut stand.st_stand_basic_data;
+    if same_content(&before.st_stand_basic_data, basic_data)? {
+        basic_data.co_change_state = Some(ChangeState::Unchanged);
+    } else {
+        basic_data.co_change_state = Some(ChangeState::Updated);
+        basic_data.co_change_time = Some(time.clone());
+        changed = true;
+    }
+
+    let before_dates = dates_of(before);
+    if !before_dates.is_empty() || stand.ts_tree_stand_data.is_some() {
+        let dates = &mut stand.ts_tree_stand_data.get_or_insert_with(empty_tree_stand_data).ts_tree_stand_data_date;
+        for date in dates.iter_mut() {
+            let before_date = before_dates.iter().find(|before| same_date(before, date));
+            changed |= mark_entries(tree_strata_of(before_date), tree_strata_mut(date), time)?;
+            changed |= mark_entries(dead_tree_strata_of(before_date), dead_tree_strata_mut(date), time)?;
+            changed |= mark_summary(before_date.and_then(|before| before.tss_tree_stand_summary.as_ref()), date, time)?;
+            remove_empty_strata(date);
+        }
+        // Tree stand data dates have no ChangeState of their own, so a removed
+        // date is sent with its strata and summary deleted
+        let removed: Vec<_> = before_dates.iter().filter(|before| !dates.iter().any(|date| same_date(before, date))).cloned().collect();
+        for mut date in removed {
+            set_date_deep(&mut date, ChangeState::Deleted, time);
+            dates.push(date);
+            changed = true;
+        }
+    }
+
+    let before_operations = operations_of(before);
+    if !before_operations.is_empty() || stand.op_operations.is_some() {
+        let operations = stand.op_operations.get_or_insert_with(|| OpOperations { text: None, op_operation: Vec::new() });
+        changed |= mark_entries(before_operations, &mut operations.op_operation, time)?;
+    }
+
+    let before_features = special_features_of(before);
+    if !before_features.is_empty() || stand.st_special_features.is_some() {
+        let features = stand.st_special_features.get_or_insert_with(|| StSpecialFeatures { text: None, st_special_feature: Vec::new() });
+        changed |= mark_entries(before_features, &mut features.st_special_feature, time)?;
+    }
+
+    Ok(changed)
+}
+
+// Marks edited entries as unchanged, updated or inserted and appends the
+// original entries that were removed as deleted. Returns false if nothing changed.
+fn mark_entries<T: Tracked>(original: &[T], edited: &mut Vec<T>, time: &Typed<NaiveDateTime>) -> Result<bool, ForestDataError> {
+    let mut changed = false;
+
+    for entry in edited.iter_mut() {
+        match original.iter().find(|before| before.id() == entry.id()) {
+            Some(before) => {
+                entry.mark_nested(before, time)?;
+                if same_content(before, entry)? {
+                    entry.set_change(ChangeState::Unchanged, None);
+                } else {
+                    entry.set_change(ChangeState::Updated, Some(time));
+                    changed = true;
+                }
+            }
+            None => {
+                entry.set_change_deep(ChangeState::Inserted, time);
+                changed = true;
+            }
+        }
+    }
+
+    let removed: Vec<_> = original.iter().filter(|before| !edited.iter().any(|entry| entry.id() == before.id())).cloned().collect();
+    for mut entry in removed {
+        entry.set_change_deep(ChangeState::Deleted, time);
+        edited.push(entry);
+        changed = true;
+    }
+
+    Ok(changed)
+}
+
+// Marks the summary of an edited date against the summary of the original
+// date, or adds the original summary as deleted. A date has one summary, so
+// it is matched by the date rather than by id.
+fn mark_summary(
+    original: Option<&TssTreeStandSummary>,
+    date: &mut TsTreeStandDataDate,
+    time: &Typed<NaiveDateTime>,
+) -> Result<bool, ForestDataError> {
+    match (original, date.tss_tree_stand_summary.as_mut()) {
+        (Some(before), Some(summary)) => {
+            if same_content(before, summary)? {
+                summary.set_change(ChangeState::Unchanged, None);
+                Ok(false)
+            } else {
+                summary.set_change(ChangeState::Updated, Some(time));
+                Ok(true)
+            }
+        }
+        (None, Some(summary)) => {
+            summary.set_change(ChangeState::Inserted, Some(time));
+            Ok(true)
+        }
+        (Some(before), None) => {
+            let mut summary = before.clone();
+            summary.set_change(ChangeState::Deleted, Some(time));
+            date.tss_tree_stand_summary = Some(summary);
+            Ok(true)
+        }
+        (None, None) => Ok(false),
+    }
+}
+
+// Applies the changes to `base`: deleted entries are removed and the other
+// entries inserted or replaced
+fn apply_entries<T: Tracked>(base: &[T], changes: &[T]) -> Vec<T> {
+    let mut entries = base.to_vec();
+
+    for change in changes {
+        let position = entries.iter().position(|entry| entry.id() == change.id());
+        if change.change_state() == Some(&ChangeState::Deleted) {
+            if let Some(position) = position {
+                entries.remove(position);
+            }
+            continue;
+        }
+
+        let mut entry = change.clone();
+        entry.apply_nested(position.map(|position| &entries[position]));
+        match position {
+            Some(position) => entries[position] = entry,
+            None => entries.push(entry),
+        }
+    }
+
+    entries
+}
+
+fn applied_stand(change: &StStand, base: Option<&StStand>) -> StStand {
+    let mut stand = change.clone();
+
+    let base_dates = base.map(dates_of).unwrap_or_default();
+    let mut dates = base_dates.to_vec();
+    for change_date in dates_of(change) {
+        let base_date = base_dates.iter().find(|date| same_date(date, change_date));
+        let mut date = change_date.clone();
+        *tree_strata_mut(&mut date) = apply_entries(tree_strata_of(base_date), tree_strata_of(Some(change_date)));
+        *dead_tree_strata_mut(&mut date) = apply_entries(dead_tree_strata_of(base_date), dead_tree_strata_of(Some(change_date)));
+        date.tss_tree_stand_summary = match &change_date.tss_tree_stand_summary {
+            Some(summary) if summary.change_state() == Some(&ChangeState::Deleted) => None,
+            Some(summary) => Some(summary.clone()),
+            None => base_date.and_then(|date| date.tss_tree_stand_summary.clone()),
+        };
+        remove_empty_strata(&mut date);
+        let position = dates.iter().position(|date| same_date(date, change_date));
+        // A removed date comes with all of its contents deleted
+        let removed = date.tst_tree_strata.is_none() && date.dts_dead_tree_strata.is_none() && date.tss_tree_stand_summary.is_none();
+        match (position, removed) {
+            (Some(position), true) => {
+                dates.remove(position);
+            }
+            (Some(position), false) => dates[position] = date,
+            (None, true) => {}
+            (None, false) => dates.push(date),
+        }
+    }
+    stand.ts_tree_stand_data = if dates.is_empty() {
+        None
+    } else {
+        Some(TsTreeStandData { ts_tree_stand_data_date: dates, ..change.ts_tree_stand_data.clone().unwrap_or_else(empty_tree_stand_data) })
+    };
+
+    let operations = apply_entries(base.map(operations_of).unwrap_or_default(), operations_of(change));
+    stand.op_operations = (!operations.is_empty()).then_some(OpOperations { text: None, op_operation: operations });
+
+    let features = apply_entries(base.map(special_features_of).unwrap_or_default(), special_features_of(change));
+    stand.st_special_features = (!features.is_empty()).then_some(StSpecialFeatures { text: None, st_special_feature: features });
+
+    stand
+}
+
+fn set_stand_deep(stand: &mut StStand, state: ChangeState, time: &Typed<NaiveDateTime>) {
+    stand.st_stand_basic_data.co_change_state = Some(state.clone());
+    stand.st_stand_basic_data.co_change_time = Some(time.clone());
+    for date in stand.ts_tree_stand_data.iter_mut().flat_map(|data| data.ts_tree_stand_data_date.iter_mut()) {
+        set_date_deep(date, state.clone(), time);
+    }
+    for operation in stand.op_operations.iter_mut().flat_map(|operations| operations.op_operation.iter_mut()) {
+        operation.set_change_deep(state.clone(), time);
+    }
+    for feature in stand.st_special_features.iter_mut().flat_map(|features| features.st_special_feature.iter_mut()) {
+        feature.set_change_deep(state.clone(), time);
+    }
+}
+
+fn set_date_deep(date: &mut TsTreeStandDataDate, state: ChangeState, time: &Typed<NaiveDateTime>) {
+    for stratum in date.tst_tree_strata.iter_mut().flat_map(|strata| strata.tst_tree_stratum.iter_mut()) {
+        stratum.set_change_deep(state.clone(), time);
+    }
+    for stratum in date.dts_dead_tree_strata.iter_mut().flat_map(|strata| strata.dts_dead_tree_stratum.iter_mut()) {
+        stratum.set_change_deep(state.clone(), time);
+    }
+    if let Some(summary) = date.tss_tree_stand_summary.as_mut() {
+        summary.set_change_deep(state, time);
+    }
+}
+
+fn remove_empty_strata(date: &mut TsTreeStandDataDate) {
+    if date.tst_tree_strata.as_ref().is_some_and(|strata| strata.tst_tree_stratum.is_empty()) {
+        date.tst_tree_strata = None;
+    }
+    if date.dts_dead_tree_strata.as_ref().is_some_and(|strata| strata.dts_dead_tree_stratum.is_empty()) {
+        date.dts_dead_tree_strata = None;
+    }
+}
+
+// Compares two elements by their values, ignoring the ChangeState and
+// ChangeTime fields at any depth
+fn same_content<T: Serialize>(a: &T, b: &T) -> Result<bool, ForestDataError> {
+    let to_value = |v: &T| with_canonical_text(|| serde_json::to_value(v)).map_err(|e| ForestDataError::Serialization(e.to_string()));
+    let (mut a, mut b) = (to_value(a)?, to_value(b)?);
+    strip_change_fields(&mut a);
+    strip_change_fields(&mut b);
+    Ok(a == b)
+}
+
+fn strip_change_fields(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            object.remove("ChangeState");
+            object.remove("ChangeTime");
+            object.values_mut().for_each(strip_change_fields);
+        }
+        Value::Array(entries) => entries.iter_mut().for_each(strip_change_fields),
+        _ => {}
+    }
+}
+
+fn same_date(a: &TsTreeStandDataDate, b: &TsTreeStandDataDate) -> bool {
+    a.date == b.date && a.ts_tree_stand_data_date_type == b.ts_tree_stand_data_date_type
+}
+
+fn dates_of(stand: &StStand) -> &[TsTreeStandDataDate] {
+    stand.ts_tree_stand_data.as_ref().map(|data| data.ts_tree_stand_data_date.as_slice()).unwrap_or_default()
+}
+
+fn operations_of(stand: &StStand) -> &[OpOperation] {
+    stand.op_operations.as_ref().map(|operations| operations.op_operation.as_slice()).unwrap_or_default()
+}
+
+fn special_features_of(stand: &StStand) -> &[StSpecialFeature] {
+    stand.st_special_features.as_ref().map(|features| features.st_special_feature.as_slice()).unwrap_or_default()
+}
+
+fn tree_strata_of(date: Option<&TsTreeStandDataDate>) -> &[TstTreeStratum] {
+    date.and_then(|date| date.tst_tree_strata.as_ref()).map(|strata| strata.tst_tree_stratum.as_slice()).unwrap_or_default()
+}
+
+fn dead_tree_strata_of(date: Option<&TsTreeStandDataDate>) -> &[DtsDeadTreeStratum] {
+    date.and_then(|date| date.dts_dead_tree_strata.as_ref()).map(|strata| strata.dts_dead_tree_stratum.as_slice()).unwrap_or_default()
+}
+
+fn tree_strata_mut(date: &mut TsTreeStandDataDate) -> &mut Vec<TstTreeStratum> {
+    &mut date.tst_tree_strata.get_or_insert_with(|| TstTreeStrata { text: None, tst_tree_stratum: Vec::new() }).tst_tree_stratum
+}
+
+fn dead_tree_strata_mut(date: &mut TsTreeStandDataDate) -> &mut Vec<DtsDeadTreeStratum> {
+    &mut date.dts_dead_tree_strata.get_or_insert_with(|| DtsDeadTreeStrata { text: None, dts_dead_tree_stratum: Vec::new() }).dts_dead_tree_stratum
+}
+
+fn empty_stands() -> StStands {
+    StStands { text: None, st_stand: Vec::new() }
+}
+
+fn empty_tree_stand_data() -> TsTreeStandData {
+    TsTreeStandData { text: None, ts_tree_stand_data_date: Vec::new() }
+}
+
+// The parcel with the ids of `parcel` in `data`, added with the real estate
+// and parcel data of `estate` and `parcel` if it is missing
+fn parcel_in<'a>(data: &'a mut ForestPropertyData, estate: &ReRealEstate, parcel: &ReParcel) -> &'a mut ReParcel {
+    let estates = &mut data.re_real_estates.get_or_insert_with(|| ReRealEstates { text: None, re_real_estate: Vec::new() }).re_real_estate;
+    let estate_index = match estates.iter().position(|e| e.id == estate.id) {
+        Some(index) => index,
+        None => {
+            let mut shell = estate.clone();
+            shell.re_parcels.re_parcel.clear();
+            estates.push(shell);
+            estates.len() - 1
+        }
+    };
+
+    let parcels = &mut estates[estate_index].re_parcels.re_parcel;
+    let parcel_index = match parcels.iter().position(|p| p.id == parcel.id) {
+        Some(index) => index,
+        None => {
+            let mut shell = parcel.clone();
+            shell.st_stands.st_stand.clear();
+            parcels.push(shell);
+            parcels.len() - 1
+        }
+    };
+    &mut parcels[parcel_index]
+}
+
+fn retain_changed<F>(stands: &mut Vec<StStand>, mark: &mut F) -> Result<(), ForestDataError>
+where
+    F: FnMut(&mut StStand) -> Result<bool, ForestDataError>,
+{
+    let mut kept = Vec::with_capacity(stands.len());
+    for stand in stands.iter_mut() {
+        kept.push(mark(stand)?);
+    }
+    let mut kept = kept.into_iter();
+    stands.retain(|_| kept.next() == Some(true));
+    Ok(())
+}
+
+fn remove_stand(data: &mut ForestPropertyData, id: &str) {
+    if let Some(stands) = data.st_stands.as_mut() {
+        stands.st_stand.retain(|stand| stand.id != id);
+    }
+    for estate in data.re_real_estates.iter_mut().flat_map(|estates| estates.re_real_estate.iter_mut()) {
+        for parcel in estate.re_parcels.re_parcel.iter_mut() {
+            parcel.st_stands.st_stand.retain(|stand| stand.id != id);
+        }
+    }
+}
+
+// Removes parcels, real estates and root stand lists that have no stands
+fn remove_empty(data: &mut ForestPropertyData) {
+    if data.st_stands.as_ref().is_some_and(|stands| stands.st_stand.is_empty()) {
+        data.st_stands = None;
+    }
+    if let Some(estates) = data.re_real_estates.as_mut() {
+        for estate in estates.re_real_estate.iter_mut() {
+            estate.re_parcels.re_parcel.retain(|parcel| !parcel.st_stands.st_stand.is_empty());
+        }
+        estates.re_real_estate.retain(|estate| !estate.re_parcels.re_parcel.is_empty());
+        if estates.re_real_estate.is_empty() {
+            data.re_real_estates = None;
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use std::fmt;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// A code that is not well-formed for its code list, or not listed in a
+// closed code list
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidCode {
+    pub list: &'static str,
+    pub code: String,
+}
+
+impl fmt::Display for InvalidCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {} code `{}`", self.list, self.code)
+    }
+}
+
+impl std::error::Error for InvalidCode {}
+
+// Codes in the MV code lists are short alphanumeric strings.
+// Empty codes occur in ForestKIT exports and are kept as `Unknown`.
+fn is_well_formed(code: &str) -> bool {
+    code.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+// Defines an enum for a Metsäkeskus MV code list.
+//
+// By default a code list is open: codes that are well-formed but not listed
+// parse as `Unknown` with their code and are written back unchanged, so
+// `Unknown` is the fallback and not an error. Lists marked `closed` have no
+// `Unknown` variant and reject every code that is not listed.
+macro_rules! code_list {
+    (@impls $name:ident) => {
+        // Prints "Mänty / Scots pine", or the bare code if it is not listed
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match (self.finnish_name(), self.english_name()) {
+                    (Some(fi), Some(en)) => write!(f, "{} / {}", fi, en),
+                    _ => f.write_str(self.code()),
+                }
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.code())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let code = String::deserialize(deserializer)?;
+                $name::try_from(code.as_str()).map_err(serde::de::Error::custom)
+            }
+        }
+    };
+    ($(#[$meta:meta])* closed $name:ident { $($variant:ident = $code:literal, $fi:literal, $en:literal;)* }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant,)*
+        }
+
+        impl $name {
+            pub fn code(&self) -> &str {
+                match self {
+                    $($name::$variant => $code,)*
+                }
+            }
+
+            pub fn finnish_name(&self) -> Option<&'static str> {
+                match self {
+                    $($name::$variant => Some($fi),)*
+                }
+            }
+
+            pub fn english_name(&self) -> Option<&'static str> {
+                match self {
+                    $($name::$variant => Some($en),)*
+                }
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = InvalidCode;
+
+            fn try_from(code: &str) -> Result<Self, Self::Error> {
+                let code = code.trim();
+                match code {
+                    $($code => Ok($name::$variant),)*
+                    _ => Err(InvalidCode { list: stringify!($name), code: code.to_string() }),
+                }
+            }
+        }
+
+        code_list!(@impls $name);
+    };
+    ($(#[$meta:meta])* $name:ident { $($variant:ident = $code:literal, $fi:literal, $en:literal;)* }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant,)*
+            Unknown(String),
+        }
+
+        impl $name {
+            pub fn code(&self) -> &str {
+                match self {
+                    $($name::$variant => $code,)*
+                    $name::Unknown(code) => code,
+                }
+            }
+
+            pub fn finnish_name(&self) -> Option<&'static str> {
+                match self {
+                    $($name::$variant => Some($fi),)*
+                    $name::Unknown(_) => None,
+                }
+            }
+
+            pub fn english_name(&self) -> Option<&'static str> {
+                match self {
+                    $($name::$variant => Some($en),)*
+                    $name::Unknown(_) => None,
+                }
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = InvalidCode;
+
+            fn try_from(code: &str) -> Result<Self, Self::Error> {
+                let code = code.trim();
+                match code {
+                    $($code => Ok($name::$variant),)*
+                    _ if is_well_formed(code) => Ok($name::Unknown(code.to_string())),
+                    _ => Err(InvalidCode { list: stringify!($name), code: code.to_string() }),
+                }
+            }
+        }
+
+        code_list!(@impls $name);
+    };
+}
+
+code_list! {
+    // Puulaji
+    TreeSpecies {
+        ScotsPine = "1", "Mänty", "Scots pine";
+        NorwaySpruce = "2", "Kuusi", "Norway spruce";
+        SilverBirch = "3", "Rauduskoivu", "Silver birch";
+        DownyBirch = "4", "Hieskoivu", "Downy birch";
+        Aspen = "5", "Haapa", "European aspen";
+        GreyAlder = "6", "Harmaaleppä", "Grey alder";
+        BlackAlder = "7", "Tervaleppä", "Black alder";
+        OtherConiferous = "8", "Muu havupuu", "Other coniferous tree";
+        OtherDeciduous = "9", "Muu lehtipuu", "Other deciduous tree";
+        DouglasFir = "10", "Douglaskuusi", "Douglas fir";
+        Juniper = "11", "Kataja", "Common juniper";
+        LodgepolePine = "12", "Kontortamänty", "Lodgepole pine";
+        EuropeanWhiteElm = "13", "Kynäjalava", "European white elm";
+        Larch = "14", "Lehtikuusi", "Larch";
+        SmallLeavedLime = "15", "Metsälehmus", "Small-leaved lime";
+        BlackSpruce = "16", "Mustakuusi", "Black spruce";
+        Willow = "17", "Paju", "Willow";
+        Rowan = "18", "Pihlaja", "Rowan";
+        Fir = "19", "Pihta", "Fir";
+        GoatWillow = "20", "Raita", "Goat willow";
+        Ash = "21", "Saarni", "European ash";
+        SwissStonePine = "22", "Sembramänty", "Swiss stone pine";
+        SerbianSpruce = "23", "Serbiankuusi", "Serbian spruce";
+        Oak = "24", "Tammi", "Pedunculate oak";
+        BirdCherry = "25", "Tuomi", "Bird cherry";
+        NorwayMaple = "26", "Vaahtera", "Norway maple";
+        CurlyBirch = "27", "Visakoivu", "Curly birch";
+        WychElm = "28", "Vuorijalava", "Wych elm";
+        Deciduous = "29", "Lehtipuu", "Deciduous tree";
+        Coniferous = "30", "Havupuu", "Coniferous tree";
+    }
+}
+
+code_list! {
+    // Kasvupaikka
+    FertilityClass {
+        HerbRich = "1", "Lehto", "Herb-rich forest";
+        HerbRichHeath = "2", "Lehtomainen kangas", "Herb-rich heath forest";
+        MesicHeath = "3", "Tuore kangas", "Mesic heath forest";
+        SubXericHeath = "4", "Kuivahko kangas", "Sub-xeric heath forest";
+        XericHeath = "5", "Kuiva kangas", "Xeric heath forest";
+        BarrenHeath = "6", "Karukkokangas", "Barren heath forest";
+        RockyOrSandy = "7", "Kalliomaa tai hietikko", "Rocky or sandy soil";
+        SummitOrFell = "8", "Lakimetsä tai tunturi", "Summit forest or fell";
+    }
+}
+
+code_list! {
+    // Maalaji
+    SoilType {
+        MediumCoarseMineral = "10", "Keskikarkea tai karkea kangasmaa", "Medium-coarse or coarse mineral soil";
+        CoarseTill = "11", "Karkea moreeni", "Coarse till";
+        CoarseSorted = "12", "Karkea lajittunut maalaji", "Coarse sorted soil";
+        FineMineral = "20", "Hienojakoinen kangasmaa", "Fine-grained mineral soil";
+        FineTill = "21", "Hienojakoinen moreeni", "Fine-grained till";
+        FineSorted = "22", "Hienojakoinen lajittunut maalaji", "Fine-grained sorted soil";
+        Silt = "23", "Silttipitoinen maalaji", "Silty soil";
+        Clay = "24", "Savimaa", "Clay soil";
+        StonyMediumCoarseMineral = "30", "Kivinen keskikarkea tai karkea kangasmaa", "Stony medium-coarse or coarse mineral soil";
+        StonyCoarseTill = "31", "Kivinen karkea moreeni", "Stony coarse till";
+        StonyCoarseSorted = "32", "Kivinen karkea lajittunut maalaji", "Stony coarse sorted soil";
+        StonyFineMineral = "40", "Kivinen hienojakoinen kangasmaa", "Stony fine-grained mineral soil";
+        RockOrBoulder = "50", "Kallio tai kivikko", "Rock or boulder field";
+        Peat = "60", "Turvemaa", "Peatland";
+        SedgePeat = "61", "Saraturve", "Sedge peat";
+        SphagnumPeat = "62", "Rahkaturve", "Sphagnum peat";
+        WoodyPeat = "63", "Puuvaltainen turve", "Woody peat";
+        Mould = "70", "Multamaa", "Mould";
+        Gyttja = "80", "Liejumaa", "Gyttja";
+    }
+}
+
+code_list! {
+    // Ojitustilanne
+    DrainageState {
+        UndrainedMineral = "1", "Ojittamaton kangas", "Undrained mineral soil";
+        DrainedMineral = "2", "Ojitettu kangas", "Drained mineral soil";
+        Paludified = "3", "Soistunut kangas", "Paludified mineral soil";
+        UndrainedPeatland = "6", "Ojittamaton suo", "Undrained peatland";
+        RecentlyDrained = "7", "Ojikko", "Recently drained peatland";
+        Transforming = "8", "Muuttuma", "Transforming drained peatland";
+        PeatlandForest = "9", "Turvekangas", "Drained peatland forest";
+    }
+}
+
+code_list! {
+    // Kehitysluokka
+    DevelopmentClass {
+        OpenArea = "A0", "Aukea", "Open regeneration area";
+        SeedTreeStand = "S0", "Siemenpuumetsikkö", "Seed tree stand";
+        YoungSeedlingStand = "T1", "Taimikko alle 1,3 m", "Seedling stand under 1.3 m";
+        AdvancedSeedlingStand = "T2", "Taimikko yli 1,3 m", "Seedling stand over 1.3 m";
+        SeedlingStandWithHoldovers = "Y1", "Ylispuustoinen taimikko", "Seedling stand with holdover trees";
+        YoungThinningStand = "02", "Nuori kasvatusmetsikkö", "Young thinning stand";
+        AdvancedThinningStand = "03", "Varttunut kasvatusmetsikkö", "Advanced thinning stand";
+        MatureStand = "04", "Uudistuskypsä metsikkö", "Mature stand";
+        ShelterwoodStand = "05", "Suojuspuumetsikkö", "Shelterwood stand";
+        UnevenAged = "ER", "Eri-ikäisrakenteinen metsä", "Uneven-aged forest";
+    }
+}
+
+code_list! {
+    // Pääryhmä
+    MainGroup {
+        ForestLand = "1", "Metsämaa", "Forest land";
+        PoorlyProductive = "2", "Kitumaa", "Poorly productive forest land";
+        Unproductive = "3", "Joutomaa", "Unproductive land";
+        OtherForestryLand = "4", "Muu metsätalousmaa", "Other forestry land";
+        BuildingSite = "5", "Tontti", "Building site";
+        Agricultural = "6", "Maatalousmaa", "Agricultural land";
+        OtherLand = "7", "Muu maa", "Other land";
+        Water = "8", "Vesistö", "Water";
+    }
+}
+
+code_list! {
+    // Toimenpidelaji. The cutting codes and the silviculture codes whose
+    // meaning the sample exports confirm are listed. Other codes, e.g. 302,
+    // 410 and 660, parse as `Unknown` with their code.
+    OperationType {
+        HoldoverRemoval = "1", "Ylispuiden poisto", "Removal of holdover trees";
+        FirstThinning = "2", "Ensiharvennus", "First thinning";
+        Thinning = "3", "Harvennushakkuu", "Thinning";
+        StripCutting = "4", "Kaistalehakkuu", "Strip cutting";
+        ClearCutting = "5", "Avohakkuu", "Clear cutting";
+        VeilTreeCutting = "6", "Verhopuuhakkuu", "Veil tree cutting";
+        ShelterwoodCutting = "7", "Suojuspuuhakkuu", "Shelterwood cutting";
+        SeedTreeCutting = "8", "Siemenpuuhakkuu", "Seed tree cutting";
+        SpecialCutting = "9", "Erikoishakkuu", "Special cutting";
+        SelectionCutting = "10", "Poimintahakkuu", "Selection cutting";
+        GapCutting = "11", "Pienaukkohakkuu", "Gap cutting";
+        ReleaseCutting = "12", "Väljennyshakkuu", "Release cutting";
+        UnevenAgedCutting = "14", "Eri-ikäisrakenteinen hakkuu", "Uneven-aged cutting";
+        PreClearing = "101", "Ennakkoraivaus", "Pre-clearing";
+        Sowing = "510", "Kylvö", "Sowing";
+        Planting = "520", "Istutus", "Planting";
+        SeedlingStandTending = "740", "Taimikonhoito", "Seedling stand tending";
+        YoungStandTending = "750", "Nuoren metsän hoito", "Young stand tending";
+    }
+}
+
+code_list! {
+    // Ehdotuksen tyyppi
+    closed ProposalType {
+        Proposal = "0", "Ehdotus", "Proposal";
+        Urgent = "1", "Kiireellinen", "Urgent";
+    }
+}
+
+code_list! {
+    // Erityispiirre. The code list is long and revised between schema
+    // versions, so no codes are listed and every code parses as `Unknown`.
+    FeatureCode {}
+}
+
+code_list! {
+    // Muutostila. Tells the receiver of a change-set what to do with an element.
+    closed ChangeState {
+        Unchanged = "0", "Ei muutosta", "Unchanged";
+        Inserted = "1", "Uusi", "Inserted";
+        Updated = "2", "Muuttunut", "Updated";
+        Deleted = "3", "Poistettu", "Deleted";
+    }
+}
+
+code_list! {
+    // Puustotiedon tyyppi, the `type` of a TreeStandDataDate
+    closed TreeStandDataType {
+        Measured = "1", "Inventointi", "Measured";
+        Updated = "2", "Ajantasaistus", "Updated";
+        Forecast = "3", "Ennuste", "Forecast";
+    }
+}
+
+code_list! {
+    // Puutavaralaji. Most Metsäkeskus cutting proposals use saw log and pulp
+    // wood, but other codes such as 8 and 11 occur without a tree species;
+    // they are kept as `Unknown`.
+    StemType {
+        SawLog = "1", "Tukki", "Saw log";
+        PulpWood = "5", "Kuitu", "Pulp wood";
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use crate::error::{ForestDataError, XmlError};
+
+// A difference between two XML documents found by `compare_xml`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Difference {
+    DroppedElement { path: String },
+    AddedElement { path: String },
+    DroppedAttribute { path: String, name: String },
+    AddedAttribute { path: String, name: String },
+    ChangedAttribute { path: String, name: String, before: String, after: String },
+    ChangedText { path: String, before: String, after: String },
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difference::DroppedElement { path } => write!(f, "dropped element {}", path),
+            Difference::AddedElement { path } => write!(f, "added element {}", path),
+            Difference::DroppedAttribute { path, name } => write!(f, "dropped attribute {}@{}", path, name),
+            Difference::AddedAttribute { path, name } => write!(f, "added attribute {}@{}", path, name),
+            Difference::ChangedAttribute { path, name, before, after } => {
+                write!(f, "changed attribute {}@{}: `{}` -> `{}`", path, name, before, after)
+            }
+            Difference::ChangedText { path, before, after } => write!(f, "changed text {}: `{}` -> `{}`", path, before, after),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    name: String,
+    attributes: BTreeMap<String, String>,
+    text: String,
+    children: Vec<Node>,
+}
+
+// Compares two XML documents by content. Element and attribute names are
+// compared without prefixes, namespace declarations, comments and whitespace
+// around text are ignored, and children are matched by name in document order.
+pub fn compare_xml(original: &str, other: &str) -> Result<Vec<Difference>, ForestDataError> {
+    let original = parse_tree(original)?;
+    let other = parse_tree(other)?;
+    let mut differences = Vec::new();
+
+    if original.name != other.name {
+        differences.push(Difference::DroppedElement { path: original.name.clone() });
+        differences.push(Difference::AddedElement { path: other.name.clone() });
+    } else {
+        compare_nodes(&original, &other, "", &mut differences);
+    }
+
+    Ok(differences)
+}
+
+fn compare_nodes(a: &Node, b: &Node, parent_path: &str, differences: &mut Vec<Difference>) {
+    let path = node_path(parent_path, a);
+
+    for (name, value) in &a.attributes {
+        match b.attributes.get(name) {
+            None => differences.push(Difference::DroppedAttribute { path: path.clone(), name: name.clone() }),
+            Some(other) if other != value => differences.push(Difference::ChangedAttribute {
+                path: path.clone(),
+                name: name.clone(),
+                before: value.clone(),
+                after: other.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for name in b.attributes.keys().filter(|name| !a.attributes.contains_key(*name)) {
+        differences.push(Difference::AddedAttribute { path: path.clone(), name: name.clone() });
+    }
+
+    if a.text != b.text {
+        differences.push(Difference::ChangedText { path: path.clone(), before: a.text.clone(), after: b.text.clone() });
+    }
+
+    let mut names: Vec<&str> = Vec::new();
+    for child in a.children.iter().chain(&b.children) {
+        if !names.contains(&child.name.as_str()) {
+            names.push(&child.name);
+        }
+    }
+
+    for name in names {
+        let a_children: Vec<&Node> = a.children.iter().filter(|c| c.name == name).collect();
+        let b_children: Vec<&Node> = b.children.iter().filter(|c| c.name == name).collect();
+
+        for (i, child) in a_children.iter().enumerate() {
+            match b_children.get(i) {
+                Some(other) => compare_nodes(child, other, &path, differences),
+                None => differences.push(Difference::DroppedElement { path: node_path(&path, child) }),
+            }
+        }
+        for child in b_children.iter().skip(a_children.len()) {
+            differences.push(Difference::AddedElement { path: node_path(&path, child) });
+        }
+    }
+}
+
+fn node_path(parent_path: &str, node: &Node) -> String {
+    let name = match node.attributes.get("id") {
+        Some(id) => format!("{}[id={}]", node.name, id),
+        None => node.name.clone(),
+    };
+
+    if parent_path.is_empty() {
+        name
+    } else {
+        format!("{}/{}", parent_path, name)
+    }
+}
+
+fn parse_tree(xml: &str) -> Result<Node, ForestDataError> {
+    let mut reader = Reader::from_str(xml);
+    let mut stack: Vec<Node> = vec![Node::default()];
+
+    loop {
+        let event = reader.read_event()
+            .map_err(|e| XmlError::at(xml, reader.error_position() as usize, String::new(), e.to_string()))?;
+
+        match event {
+            Event::Start(e) => stack.push(new_node(xml, &e)?),
+            Event::Empty(e) => {
+                let node = new_node(xml, &e)?;
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(node);
+                }
+            }
+            Event::End(_) => {
+                let node = stack.pop().unwrap_or_default();
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(node);
+                }
+            }
+            Event::Text(e) => {
+                let text = e.unescape().map_err(|e| XmlError::at(xml, 0, String::new(), e.to_string()))?;
+                if let Some(node) = stack.last_mut() {
+                    node.text.push_str(text.trim());
+                }
+            }
+            Event::CData(e) => {
+                if let Some(node) = stack.last_mut() {
+                    node.text.push_str(String::from_utf8_lossy(&e).trim());
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    let document = stack.pop().unwrap_or_default();
+    Ok(document.children.into_iter().next().unwrap_or_default())
+}
+
+fn new_node(xml: &str, start: &BytesStart) -> Result<Node, ForestDataError> {
+    let mut node = Node {
+        name: String::from_utf8_lossy(start.local_name().as_ref()).to_string(),
+        ..Node::default()
+    };
+
+    for attr in start.attributes() {
+        let attr = attr.map_err(|e| XmlError::at(xml, 0, String::new(), e.to_string()))?;
+        if attr.key.as_namespace_binding().is_some() {
+            continue;
+        }
+        let value = attr.unescape_value().map_err(|e| XmlError::at(xml, 0, String::new(), e.to_string()))?;
+        node.attributes.insert(String::from_utf8_lossy(attr.key.local_name().as_ref()).to_string(), value.to_string());
+    }
+
+    Ok(node)
+}
@@ -0,0 +1,290 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use serde::Serialize;
+use serde_json::Value;
+use crate::error::ForestDataError;
+use crate::forest_property_data::{ForestPropertyData, StStand};
+use crate::values::with_canonical_text;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityKind {
+    Stand,
+    Stratum,
+    DeadTreeStratum,
+    Operation,
+    SpecialFeature,
+}
+
+impl fmt::Display for EntityKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            EntityKind::Stand => "stand",
+            EntityKind::Stratum => "stratum",
+            EntityKind::DeadTreeStratum => "dead tree stratum",
+            EntityKind::Operation => "operation",
+            EntityKind::SpecialFeature => "special feature",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Modified => "modified",
+        })
+    }
+}
+
+// A changed value, `None` when the field is missing on that side
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    // Path of the field below the entity, e.g. `StandBasicData/Area`
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EntityChange {
+    pub kind: EntityKind,
+    pub id: String,
+    // The stand the entity belongs to, the stand itself for stands
+    pub stand_id: String,
+    pub change: ChangeKind,
+    // Only set for modified entities
+    pub fields: Vec<FieldChange>,
+}
+
+impl fmt::Display for EntityChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.change, self.kind, self.id)?;
+        if self.kind != EntityKind::Stand {
+            write!(f, " in stand {}", self.stand_id)?;
+        }
+        for field in &self.fields {
+            let before = field.before.as_deref().unwrap_or("-");
+            let after = field.after.as_deref().unwrap_or("-");
+            write!(f, "\n  {}: {} -> {}", field.field, before, after)?;
+        }
+        Ok(())
+    }
+}
+
+// Differences between two versions of the same forest data, in the stand
+// order of the first document followed by the stands added in the second
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DataDiff {
+    pub changes: Vec<EntityChange>,
+}
+
+impl DataDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn count(&self, change: ChangeKind) -> usize {
+        self.changes.iter().filter(|c| c.change == change).count()
+    }
+}
+
+impl fmt::Display for DataDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} added, {} removed, {} modified",
+            self.count(ChangeKind::Added),
+            self.count(ChangeKind::Removed),
+            self.count(ChangeKind::Modified),
+        )?;
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+// Compares two versions of the same data by stand id, wherever the stands are
+// in the document. Strata, operations and special features are matched by id
+// within their stand and reported separately; an added or removed stand is
+// reported without its contents. Values are compared by their typed value, so
+// e.g. "5.2" and "5.20" are not a change.
+pub fn diff(a: &ForestPropertyData, b: &ForestPropertyData) -> Result<DataDiff, ForestDataError> {
+    let mut changes = Vec::new();
+    let stands_a: HashSet<&str> = a.stands().map(|stand| stand.id.as_str()).collect();
+    let mut stands_b: HashMap<&str, &StStand> = HashMap::new();
+    for stand in b.stands() {
+        stands_b.entry(stand.id.as_str()).or_insert(stand);
+    }
+
+    for before in a.stands() {
+        match stands_b.get(before.id.as_str()) {
+            Some(after) => diff_stand(before, after, &mut changes)?,
+            None => changes.push(entity_change(EntityKind::Stand, &before.id, &before.id, ChangeKind::Removed)),
+        }
+    }
+    for after in b.stands().filter(|s| !stands_a.contains(s.id.as_str())) {
+        changes.push(entity_change(EntityKind::Stand, &after.id, &after.id, ChangeKind::Added));
+    }
+
+    Ok(DataDiff { changes })
+}
+
+fn entity_change(kind: EntityKind, id: &str, stand_id: &str, change: ChangeKind) -> EntityChange {
+    EntityChange { kind, id: id.to_string(), stand_id: stand_id.to_string(), change, fields: Vec::new() }
+}
+
+fn diff_stand(a: &StStand, b: &StStand, changes: &mut Vec<EntityChange>) -> Result<(), ForestDataError> {
+    let (stand_a, children_a) = split_stand(a)?;
+    let (stand_b, children_b) = split_stand(b)?;
+
+    let fields = field_changes(&stand_a, &stand_b);
+    if !fields.is_empty() {
+        changes.push(EntityChange { fields, ..entity_change(EntityKind::Stand, &a.id, &a.id, ChangeKind::Modified) });
+    }
+
+    let keys_a: HashSet<_> = children_a.iter().map(Child::key).collect();
+    let mut lookup_b = HashMap::new();
+    for child in &children_b {
+        lookup_b.entry(child.key()).or_insert(child);
+    }
+
+    for before in &children_a {
+        match lookup_b.get(&before.key()) {
+            Some(after) => {
+                let fields = field_changes(before, after);
+                if !fields.is_empty() {
+                    changes.push(EntityChange { fields, ..entity_change(before.kind, &before.id, &a.id, ChangeKind::Modified) });
+                }
+            }
+            None => changes.push(entity_change(before.kind, &before.id, &a.id, ChangeKind::Removed)),
+        }
+    }
+    for after in children_b.iter().filter(|after| !keys_a.contains(&after.key())) {
+        changes.push(entity_change(after.kind, &after.id, &a.id, ChangeKind::Added));
+    }
+
+    Ok(())
+}
+
+// A stand without its children, or a stratum, operation or special feature,
+// as JSON with the original text and with the canonical text of typed values
+struct Child {
+    kind: EntityKind,
+    id: String,
+    value: Value,
+    canonical: Value,
+}
+
+impl Child {
+    fn key(&self) -> (EntityKind, &str) {
+        (self.kind, &self.id)
+    }
+}
+
+// Separates the strata, operations and special features from the rest of the stand
+fn split_stand(stand: &StStand) -> Result<(Child, Vec<Child>), ForestDataError> {
+    let to_value = |stand| serde_json::to_value(stand).map_err(|e| ForestDataError::Serialization(e.to_string()));
+    let (value, children) = split_value(to_value(stand)?);
+    let (canonical, canonical_children) = split_value(with_canonical_text(|| to_value(stand))?);
+
+    let stand = Child { kind: EntityKind::Stand, id: stand.id.clone(), value, canonical };
+    let children = children.into_iter()
+        .zip(canonical_children)
+        .map(|(child, canonical)| Child { canonical: canonical.value, ..child })
+        .collect();
+    Ok((stand, children))
+}
+
+fn split_value(mut value: Value) -> (Value, Vec<Child>) {
+    let mut children = Vec::new();
+
+    if let Some(dates) = value.pointer_mut("/TreeStandData/TreeStandDataDate").and_then(Value::as_array_mut) {
+        for date in dates {
+            take_children(date, "TreeStrata", EntityKind::Stratum, &mut children);
+            take_children(date, "DeadTreeStrata", EntityKind::DeadTreeStratum, &mut children);
+        }
+    }
+    take_children(&mut value, "Operations", EntityKind::Operation, &mut children);
+    take_children(&mut value, "SpecialFeatures", EntityKind::SpecialFeature, &mut children);
+
+    (value, children)
+}
+
+// Removes the wrapper element `key`, e.g. Operations, and collects the
+// entries of its list by id
+fn take_children(value: &mut Value, key: &str, kind: EntityKind, children: &mut Vec<Child>) {
+    let Some(Value::Object(wrapper)) = value.as_object_mut().and_then(|object| object.remove(key)) else {
+        return;
+    };
+    let entries = wrapper.into_iter().find_map(|(_, v)| match v {
+        Value::Array(entries) => Some(entries),
+        _ => None,
+    });
+    for entry in entries.unwrap_or_default() {
+        let id = entry.get("@id").and_then(Value::as_str).unwrap_or_default().to_string();
+        children.push(Child { kind, id, value: entry, canonical: Value::Null });
+    }
+}
+
+// Fields whose typed values differ, reported with their original text
+fn field_changes(a: &Child, b: &Child) -> Vec<FieldChange> {
+    let (fields_a, fields_b) = (flattened(&a.value), flattened(&b.value));
+    let (canonical_a, canonical_b) = (flattened(&a.canonical), flattened(&b.canonical));
+    let lookup_a: BTreeMap<_, _> = fields_a.iter().cloned().collect();
+    let lookup_b: BTreeMap<_, _> = fields_b.iter().cloned().collect();
+    let canonical_a: BTreeMap<_, _> = canonical_a.into_iter().collect();
+    let canonical_b: BTreeMap<_, _> = canonical_b.into_iter().collect();
+
+    let mut changes = Vec::new();
+    for (field, before) in &fields_a {
+        if canonical_a.get(field) != canonical_b.get(field) {
+            changes.push(FieldChange { field: field.clone(), before: Some(before.clone()), after: lookup_b.get(field).cloned() });
+        }
+    }
+    for (field, after) in fields_b.into_iter().filter(|(field, _)| !lookup_a.contains_key(field)) {
+        changes.push(FieldChange { field, before: None, after: Some(after) });
+    }
+    changes
+}
+
+fn flattened(value: &Value) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+    flatten(value, String::new(), &mut fields);
+    fields
+}
+
+// Collects the leaf values by path. List entries are labelled by their id
+// when they have one, so reordered entries are still matched.
+fn flatten(value: &Value, path: String, fields: &mut Vec<(String, String)>) {
+    match value {
+        Value::Null => {}
+        Value::Object(object) => {
+            for (key, value) in object.iter().filter(|(key, _)| *key != "$text") {
+                let path = if path.is_empty() { key.clone() } else { format!("{}/{}", path, key) };
+                flatten(value, path, fields);
+            }
+        }
+        Value::Array(entries) => {
+            for (i, entry) in entries.iter().enumerate() {
+                let label = match entry.get("@id").and_then(Value::as_str) {
+                    Some(id) => format!("{}[id={}]", path, id),
+                    None => format!("{}[{}]", path, i),
+                };
+                flatten(entry, label, fields);
+            }
+        }
+        Value::String(s) => fields.push((path, s.clone())),
+        other => fields.push((path, other.to_string())),
+    }
+}
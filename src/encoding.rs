@@ -0,0 +1,70 @@
+use std::fs;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use crate::error::ForestDataError;
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+// Reads an XML file and decodes it according to its BOM or XML declaration
+pub fn read_xml_file(path: &str) -> Result<String, ForestDataError> {
+    let bytes = fs::read(path)?;
+    decode_xml(&bytes)
+}
+
+// Decodes raw XML bytes into a string.
+// The encoding is taken from a UTF-16 byte order mark, or from the `encoding`
+// attribute of the XML declaration, defaulting to UTF-8. Any number of leading
+// byte order marks are removed.
+pub fn decode_xml(bytes: &[u8]) -> Result<String, ForestDataError> {
+    let mut bytes = bytes;
+    while bytes.starts_with(UTF8_BOM) {
+        bytes = &bytes[UTF8_BOM.len()..];
+    }
+
+    let encoding = detect_encoding(bytes)?;
+    let (decoded, had_errors) = encoding.decode_without_bom_handling(bytes);
+
+    if had_errors {
+        return Err(ForestDataError::Encoding(format!("Input is not valid {}", encoding.name())));
+    }
+
+    Ok(decoded.trim_start_matches('\u{feff}').to_string())
+}
+
+fn detect_encoding(bytes: &[u8]) -> Result<&'static Encoding, ForestDataError> {
+    match bytes {
+        [0xFF, 0xFE, ..] | [b'<', 0x00, b'?', 0x00, ..] => return Ok(UTF_16LE),
+        [0xFE, 0xFF, ..] | [0x00, b'<', 0x00, b'?', ..] => return Ok(UTF_16BE),
+        _ => {}
+    }
+
+    match declared_encoding(bytes) {
+        Some(label) => {
+            let encoding = Encoding::for_label(label.as_bytes())
+                .ok_or_else(|| ForestDataError::Encoding(format!("Unsupported encoding: {}", label)))?;
+
+            // A single-byte document cannot be UTF-16 whatever the declaration says
+            if encoding == UTF_16LE || encoding == UTF_16BE {
+                Ok(UTF_8)
+            } else {
+                Ok(encoding)
+            }
+        }
+        None => Ok(UTF_8),
+    }
+}
+
+// Returns the value of the `encoding` pseudo-attribute of the XML declaration
+fn declared_encoding(bytes: &[u8]) -> Option<String> {
+    if !bytes.starts_with(b"<?xml") {
+        return None;
+    }
+
+    let end = bytes.windows(2).position(|w| w == b"?>")?;
+    let declaration = String::from_utf8_lossy(&bytes[..end]);
+    let start = declaration.find("encoding")? + "encoding".len();
+    let rest = declaration[start..].trim_start().strip_prefix('=')?.trim_start();
+    let quote = rest.chars().next().filter(|&c| c == '"' || c == '\'')?;
+    let value = &rest[1..];
+
+    value.find(quote).map(|end| value[..end].to_string())
+}
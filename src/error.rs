@@ -0,0 +1,209 @@
+use std::fmt;
+use std::io;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde_path_to_error::Segment;
+
+// Errors returned by the fallible parsing and writing functions
+#[derive(Debug)]
+pub enum ForestDataError {
+    Io(io::Error),
+    Http(reqwest::Error),
+    Encoding(String),
+    Xml(XmlError),
+    Serialization(String),
+    Geometry(String),
+    Merge(String),
+    ChangeSet(String),
+    PriceTable(String),
+}
+
+// A deserialization error located in the source document
+#[derive(Debug)]
+pub struct XmlError {
+    // Element path from the root, e.g. `Stands/Stand[id=2553941]/TreeStandData`
+    pub path: String,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ForestDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForestDataError::Io(e) => write!(f, "I/O error: {}", e),
+            ForestDataError::Http(e) => write!(f, "HTTP error: {}", e),
+            ForestDataError::Encoding(msg) => write!(f, "Encoding error: {}", msg),
+            ForestDataError::Xml(e) => write!(f, "{}", e),
+            ForestDataError::Serialization(msg) => write!(f, "Serialization error: {}", msg),
+            ForestDataError::Geometry(msg) => write!(f, "Geometry error: {}", msg),
+            ForestDataError::Merge(msg) => write!(f, "Merge error: {}", msg),
+            ForestDataError::ChangeSet(msg) => write!(f, "Change-set error: {}", msg),
+            ForestDataError::PriceTable(msg) => write!(f, "Price table error: {}", msg),
+        }
+    }
+}
+
+impl fmt::Display for XmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "XML error at {} (line {}, column {}): {}", self.path, self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ForestDataError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ForestDataError::Io(e) => Some(e),
+            ForestDataError::Http(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl std::error::Error for XmlError {}
+
+impl From<io::Error> for ForestDataError {
+    fn from(e: io::Error) -> Self {
+        ForestDataError::Io(e)
+    }
+}
+
+impl From<reqwest::Error> for ForestDataError {
+    fn from(e: reqwest::Error) -> Self {
+        ForestDataError::Http(e)
+    }
+}
+
+impl From<XmlError> for ForestDataError {
+    fn from(e: XmlError) -> Self {
+        ForestDataError::Xml(e)
+    }
+}
+
+// An element of the source document, used to map a serde path back to a location
+struct Element {
+    name: String,
+    id: Option<String>,
+    position: usize,
+    children: Vec<usize>,
+}
+
+impl XmlError {
+    // Locates a deserialization error in `xml` using the path reported by serde
+    pub(crate) fn from_serde_path(xml: &str, path: &serde_path_to_error::Path, message: String) -> XmlError {
+        let (elements, syntax_error) = index_elements(xml);
+
+        // A syntax error is located where the reader stopped
+        if let Some((position, stack)) = syntax_error {
+            let path = stack.iter().skip(1).map(|&i| describe(&elements[i], None)).collect::<Vec<_>>().join("/");
+            return XmlError::at(xml, position, path, message);
+        }
+
+        if elements.is_empty() {
+            return XmlError::at(xml, 0, String::new(), message);
+        }
+
+        let mut current = 0;
+        let mut parts: Vec<String> = Vec::new();
+        let mut segments = path.iter().peekable();
+
+        while let Some(segment) = segments.next() {
+            let key = match segment {
+                Segment::Map { key } => key.as_str(),
+                _ => continue,
+            };
+
+            // Attributes and text belong to the current element
+            if key.starts_with('@') || key.starts_with('$') {
+                break;
+            }
+
+            let index = match segments.peek() {
+                Some(Segment::Seq { index }) => {
+                    let index = *index;
+                    segments.next();
+                    Some(index)
+                }
+                _ => None,
+            };
+
+            let found = elements[current].children.iter()
+                .filter(|&&child| elements[child].name == key)
+                .nth(index.unwrap_or(0));
+
+            match found {
+                Some(&child) => {
+                    parts.push(describe(&elements[child], index));
+                    current = child;
+                }
+                None => {
+                    parts.push(key.to_string());
+                    break;
+                }
+            }
+        }
+
+        XmlError::at(xml, elements[current].position, parts.join("/"), message)
+    }
+
+    pub(crate) fn at(xml: &str, position: usize, path: String, message: String) -> XmlError {
+        let position = position.min(xml.len());
+        let before = &xml.as_bytes()[..position];
+        let line = before.iter().filter(|&&b| b == b'\n').count() + 1;
+        let line_start = before.iter().rposition(|&b| b == b'\n').map_or(0, |p| p + 1);
+        let column = String::from_utf8_lossy(&before[line_start..]).chars().count() + 1;
+        let path = if path.is_empty() { "ForestPropertyData".to_string() } else { path };
+
+        XmlError { path, line, column, message }
+    }
+}
+
+fn describe(element: &Element, index: Option<usize>) -> String {
+    match (&element.id, index) {
+        (Some(id), _) => format!("{}[id={}]", element.name, id),
+        (None, Some(index)) => format!("{}[{}]", element.name, index),
+        (None, None) => element.name.clone(),
+    }
+}
+
+// Builds a tree of the elements in `xml`. If the document is not well-formed,
+// also returns the error position and the stack of open elements at that point.
+fn index_elements(xml: &str) -> (Vec<Element>, Option<(usize, Vec<usize>)>) {
+    let mut reader = Reader::from_str(xml);
+    let mut elements: Vec<Element> = Vec::new();
+    let mut stack: Vec<usize> = Vec::new();
+
+    loop {
+        let position = reader.buffer_position() as usize;
+
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let index = push_element(&mut elements, &stack, &e, position);
+                stack.push(index);
+            }
+            Ok(Event::Empty(e)) => {
+                push_element(&mut elements, &stack, &e, position);
+            }
+            Ok(Event::End(_)) => {
+                stack.pop();
+            }
+            Ok(Event::Eof) => return (elements, None),
+            Ok(_) => {}
+            Err(_) => return (elements, Some((reader.error_position() as usize, stack))),
+        }
+    }
+}
+
+fn push_element(elements: &mut Vec<Element>, stack: &[usize], start: &BytesStart, position: usize) -> usize {
+    let name = String::from_utf8_lossy(start.local_name().as_ref()).to_string();
+    let id = start.try_get_attribute("id").ok().flatten()
+        .map(|a| String::from_utf8_lossy(&a.value).to_string());
+    let index = elements.len();
+
+    elements.push(Element { name, id, position, children: Vec::new() });
+    if let Some(&parent) = stack.last() {
+        elements[parent].children.push(index);
+    }
+
+    index
+}
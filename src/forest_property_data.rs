@@ -1,9 +1,22 @@
-use std::fs;
+use std::cell::Cell;
+use std::fmt;
+use std::marker::PhantomData;
+use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
+use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
+use serde::de::{DeserializeOwned, MapAccess, SeqAccess, Visitor};
 use reqwest::blocking::get;
-use quick_xml::de::from_str;
-
-#[derive(Serialize, Deserialize)]
+use quick_xml::de::Deserializer;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use crate::codes::{ChangeState, DevelopmentClass, DrainageState, FeatureCode, FertilityClass, MainGroup, OperationType, ProposalType, SoilType, StemType, TreeSpecies, TreeStandDataType};
+use crate::encoding::{decode_xml, read_xml_file};
+use crate::error::{ForestDataError, XmlError};
+use crate::namespaces::{apply_prefixes, normalize, PrefixStyle};
+use crate::values::Typed;
+use crate::writer::{serialize_xml, skip_header_comments};
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ForestPropertyData {
     #[serde(rename = "@xmlns")]
     pub xmlns: String,
@@ -44,52 +57,156 @@ pub struct ForestPropertyData {
     #[serde(rename = "RealEstates", skip_serializing_if = "Option::is_none")]
     pub re_real_estates: Option<ReRealEstates>,
     #[serde(rename = "Stands", skip_serializing_if = "Option::is_none")]
-    pub st_stands: Option<StStands>
+    pub st_stands: Option<StStands>,
+    // Comments before the root element, e.g. the generator and schema version
+    // lines. Written as comments in XML and as `_comments` in JSON.
+    #[serde(rename = "_comments", default, skip_serializing_if = "skip_header_comments")]
+    pub header_comments: Vec<String>
 }
 
 impl ForestPropertyData {
     pub fn from_xml_file(path: &str) -> ForestPropertyData {
-        let xml = fs::read_to_string(path).expect("Could not read the XML file");
-        from_str(&xml).expect("Could not parse the XML")
+        Self::try_from_xml_file(path).expect("Could not parse the XML file")
     }
 
     pub fn from_xml_str(xml_str: &str) -> ForestPropertyData {
-        from_str(xml_str).expect("Could not parse the XML")
+        Self::try_from_xml_str(xml_str).expect("Could not parse the XML")
     }
 
     pub fn from_xml_url(url: &str) -> ForestPropertyData {
-        let xml = Self::fetch_xml_url(url).unwrap();
-        from_str(&xml).expect("Failed to parse XML")
+        Self::try_from_xml_url(url).expect("Failed to parse XML")
+    }
+
+    pub fn try_from_xml_file(path: &str) -> Result<ForestPropertyData, ForestDataError> {
+        let xml = read_xml_file(path)?;
+        Self::try_from_xml_str(&xml)
+    }
+
+    pub fn try_from_xml_str(xml_str: &str) -> Result<ForestPropertyData, ForestDataError> {
+        let mut property: ForestPropertyData = deserialize_xml(xml_str)?;
+        property.header_comments = header_comments(xml_str);
+        Ok(property)
+    }
+
+    // Serializes the root element as XML with element prefixes in the given style
+    pub fn to_xml_string(&self, style: &PrefixStyle) -> Result<String, ForestDataError> {
+        let xml = serialize_xml(self)?;
+        apply_prefixes(&xml, style, None)
+    }
+
+    pub fn try_from_xml_url(url: &str) -> Result<ForestPropertyData, ForestDataError> {
+        let xml = Self::fetch_xml_url(url)?;
+        Self::try_from_xml_str(&xml)
+    }
+
+    pub fn real_estates(&self) -> impl Iterator<Item = &ReRealEstate> {
+        self.re_real_estates.iter().flat_map(|estates| &estates.re_real_estate)
+    }
+
+    // All stands in the document, both directly under Stands and on real estate parcels
+    pub fn stands(&self) -> impl Iterator<Item = &StStand> {
+        let parcel_stands = self.real_estates()
+            .flat_map(|estate| &estate.re_parcels.re_parcel)
+            .flat_map(|parcel| &parcel.st_stands.st_stand);
+        self.st_stands.iter().flat_map(|stands| &stands.st_stand).chain(parcel_stands)
+    }
+
+    pub fn stands_mut(&mut self) -> impl Iterator<Item = &mut StStand> {
+        let parcel_stands = self.re_real_estates.iter_mut()
+            .flat_map(|estates| &mut estates.re_real_estate)
+            .flat_map(|estate| &mut estate.re_parcels.re_parcel)
+            .flat_map(|parcel| &mut parcel.st_stands.st_stand);
+        self.st_stands.iter_mut().flat_map(|stands| &mut stands.st_stand).chain(parcel_stands)
     }
-    
-    fn fetch_xml_url(url: &str) -> Option<String> {
-        match get(url) {
-            Ok(resp) => {
-                match resp.text() {
-                    Ok(text) => Some(text),
-                    Err(e) => {
-                        println!("Error: {}", e);
-                        None
-                    }
-                }
-            }
-            Err(e) => {
-                println!("Error: {}", e);
-                None
-            }
+
+    fn fetch_xml_url(url: &str) -> Result<String, ForestDataError> {
+        let resp = get(url)?.error_for_status()?;
+        decode_xml(&resp.bytes()?)
+    }
+}
+
+// Returns the comments that precede the root element
+fn header_comments(xml: &str) -> Vec<String> {
+    let mut reader = Reader::from_str(xml);
+    let mut comments = Vec::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Comment(e)) => comments.push(String::from_utf8_lossy(&e).to_string()),
+            Ok(Event::Start(_)) | Ok(Event::Empty(_)) | Ok(Event::Eof) | Err(_) => break,
+            Ok(_) => {}
         }
     }
+
+    comments
+}
+
+thread_local! {
+    // Set while a document is read from XML, where repeated elements can only
+    // be read as a sequence
+    static READING_XML: Cell<bool> = const { Cell::new(false) };
 }
 
-#[derive(Serialize, Deserialize)]
+// Deserializes `xml` after resolving its namespaces, reporting failures with
+// the element path, line and column in the original document
+pub(crate) fn deserialize_xml<T: DeserializeOwned>(xml: &str) -> Result<T, ForestDataError> {
+    struct Reset(bool);
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            READING_XML.set(self.0);
+        }
+    }
+
+    let normalized = normalize(xml)?;
+    let _reset = Reset(READING_XML.replace(true));
+    let mut deserializer = Deserializer::from_str(&normalized);
+
+    serde_path_to_error::deserialize(&mut deserializer).map_err(|e| {
+        let message = e.inner().to_string();
+        XmlError::from_serde_path(xml, e.path(), message).into()
+    })
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ReRealEstates {
     #[serde(rename = "$text")]
     pub text: Option<String>,
-    #[serde(rename = "RealEstate")]
-    pub re_real_estate: ReRealEstate
+    #[serde(rename = "RealEstate", deserialize_with = "one_or_many")]
+    pub re_real_estate: Vec<ReRealEstate>
+}
+
+// RealEstate was a single object in JSON written before it became a list;
+// such an object is read as a list of one
+fn one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    struct OneOrMany<T>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for OneOrMany<T> {
+        type Value = Vec<T>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("an element or a list of elements")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Vec<T>, A::Error> {
+            Vec::deserialize(SeqAccessDeserializer::new(seq))
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Vec<T>, A::Error> {
+            Ok(vec![T::deserialize(MapAccessDeserializer::new(map))?])
+        }
+    }
+
+    if READING_XML.get() {
+        return Vec::deserialize(deserializer);
+    }
+    deserializer.deserialize_any(OneOrMany(PhantomData))
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ReRealEstate {
     #[serde(rename = "@id")]
     pub id: String,
@@ -109,7 +226,7 @@ pub struct ReRealEstate {
     pub re_parcels: ReParcels
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ReParcels {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -117,7 +234,7 @@ pub struct ReParcels {
     pub re_parcel: Vec<ReParcel>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ReParcel {
     #[serde(rename = "@id")]
     pub id: String,
@@ -129,7 +246,7 @@ pub struct ReParcel {
     pub st_stands: StStands
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct StStands {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -137,7 +254,7 @@ pub struct StStands {
     pub st_stand: Vec<StStand>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct StStand {
     #[serde(rename = "@id")]
     pub id: String,
@@ -153,14 +270,35 @@ pub struct StStand {
     pub st_special_features: Option<StSpecialFeatures>
 }
 
-#[derive(Serialize, Deserialize)]
+impl StStand {
+    // The tree stand data with the latest date, if any
+    pub fn latest_tree_stand_data(&self) -> Option<&TsTreeStandDataDate> {
+        self.latest_tree_stand_data_where(|_| true)
+    }
+
+    // The latest measured or updated tree stand data; forecasts are skipped
+    pub fn current_tree_stand_data(&self) -> Option<&TsTreeStandDataDate> {
+        self.latest_tree_stand_data_where(|date| {
+            matches!(date.ts_tree_stand_data_date_type, TreeStandDataType::Measured | TreeStandDataType::Updated)
+        })
+    }
+
+    fn latest_tree_stand_data_where(&self, include: impl Fn(&TsTreeStandDataDate) -> bool) -> Option<&TsTreeStandDataDate> {
+        self.ts_tree_stand_data.as_ref()?
+            .ts_tree_stand_data_date.iter()
+            .filter(|date| include(date))
+            .max_by_key(|date| date.date.get())
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct StStandBasicData {
     #[serde(rename = "$text")]
     pub text: Option<String>,
     #[serde(rename = "ChangeState", skip_serializing_if = "Option::is_none")]
-    pub co_change_state: Option<String>,
+    pub co_change_state: Option<ChangeState>,
     #[serde(rename = "ChangeTime", skip_serializing_if = "Option::is_none")]
-    pub co_change_time: Option<String>,
+    pub co_change_time: Option<Typed<NaiveDateTime>>,
     #[serde(rename = "CompleteState")]
     pub st_complete_state: String,
     #[serde(rename = "Identifiers", skip_serializing_if = "Option::is_none")]
@@ -170,23 +308,23 @@ pub struct StStandBasicData {
     #[serde(rename = "StandNumberExtension", skip_serializing_if = "Option::is_none")]
     pub st_stand_number_extension: Option<String>,
     #[serde(rename = "MainGroup")]
-    pub st_main_group: String,
+    pub st_main_group: MainGroup,
     #[serde(rename = "SubGroup", skip_serializing_if = "Option::is_none")]
     pub st_sub_group: Option<String>,
     #[serde(rename = "FertilityClass", skip_serializing_if = "Option::is_none")]
-    pub st_fertility_class: Option<String>,
+    pub st_fertility_class: Option<FertilityClass>,
     #[serde(rename = "SoilType", skip_serializing_if = "Option::is_none")]
-    pub st_soil_type: Option<String>,
+    pub st_soil_type: Option<SoilType>,
     #[serde(rename = "DrainageState", skip_serializing_if = "Option::is_none")]
-    pub st_drainage_state: Option<String>,
+    pub st_drainage_state: Option<DrainageState>,
     #[serde(rename = "DitchingYear", skip_serializing_if = "Option::is_none")]
-    pub st_ditching_year: Option<String>,
+    pub st_ditching_year: Option<Typed<u32>>,
     #[serde(rename = "DevelopmentClass", skip_serializing_if = "Option::is_none")]
-    pub st_development_class: Option<String>,
+    pub st_development_class: Option<DevelopmentClass>,
     #[serde(rename = "StandQuality", skip_serializing_if = "Option::is_none")]
     pub st_stand_quality: Option<String>,
     #[serde(rename = "MainTreeSpecies", skip_serializing_if = "Option::is_none")]
-    pub st_main_tree_species: Option<String>,
+    pub st_main_tree_species: Option<TreeSpecies>,
     #[serde(rename = "Accessibility", skip_serializing_if = "Option::is_none")]
     pub st_accessibility: Option<String>,
     #[serde(rename = "CuttingRestriction", skip_serializing_if = "Option::is_none")]
@@ -194,7 +332,7 @@ pub struct StStandBasicData {
     #[serde(rename = "SilvicultureRestriction", skip_serializing_if = "Option::is_none")]
     pub st_silviculture_restriction: Option<String>,
     #[serde(rename = "StandBasicDataDate")]
-    pub st_stand_basic_data_date: String,
+    pub st_stand_basic_data_date: Typed<NaiveDate>,
     #[serde(rename = "StandInfo", skip_serializing_if = "Option::is_none")]
     pub st_stand_info: Option<String>,
     #[serde(rename = "DataSource", skip_serializing_if = "Option::is_none")]
@@ -202,14 +340,14 @@ pub struct StStandBasicData {
     #[serde(rename = "GrowthPlaceDataSource", skip_serializing_if = "Option::is_none")]
     pub st_growth_place_data_source: Option<String>,
     #[serde(rename = "Area")]
-    pub st_area: String,
+    pub st_area: Typed<f64>,
     #[serde(rename = "AreaDecrease", skip_serializing_if = "Option::is_none")]
-    pub st_area_decrease: Option<String>,
+    pub st_area_decrease: Option<Typed<f64>>,
     #[serde(rename = "PolygonGeometry")]
     pub gdt_polygon_geometry: GdtPolygonGeometry
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct StIdentifiers {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -217,7 +355,7 @@ pub struct StIdentifiers {
     pub st_identifier: Vec<StIdentifier>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct StIdentifier {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -227,7 +365,7 @@ pub struct StIdentifier {
     pub co_identifier_value: String
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GdtPolygonGeometry {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -237,7 +375,7 @@ pub struct GdtPolygonGeometry {
     pub gml_polygon_property: GmlPolygonProperty
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GmlPointProperty {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -245,7 +383,7 @@ pub struct GmlPointProperty {
     pub gml_point: GmlPoint
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GmlPoint {
     #[serde(rename = "@srsName")]
     pub srs_name: String,
@@ -255,7 +393,7 @@ pub struct GmlPoint {
     pub gml_coordinates: String
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GmlPolygonProperty {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -263,7 +401,7 @@ pub struct GmlPolygonProperty {
     pub gml_polygon: GmlPolygon
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GmlPolygon {
     #[serde(rename = "@srsName")]
     pub srs_name: String,
@@ -275,7 +413,7 @@ pub struct GmlPolygon {
     pub gml_interior: Option<Vec<GmlInterior>>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GmlInterior {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -283,7 +421,7 @@ pub struct GmlInterior {
     pub gml_linear_ring: GmlInteriorGmlLinearRing
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GmlInteriorGmlLinearRing {
     #[serde(rename = "$text", skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
@@ -291,7 +429,7 @@ pub struct GmlInteriorGmlLinearRing {
     pub gml_coordinates: String
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GmlExterior {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -299,7 +437,7 @@ pub struct GmlExterior {
     pub gml_linear_ring: GmlExteriorGmlLinearRing
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GmlExteriorGmlLinearRing {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -307,7 +445,7 @@ pub struct GmlExteriorGmlLinearRing {
     pub gml_coordinates: String
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct StSpecialFeatures {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -315,7 +453,7 @@ pub struct StSpecialFeatures {
     pub st_special_feature: Vec<StSpecialFeature>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct StSpecialFeature {
     #[serde(rename = "@id")]
     pub id: String,
@@ -324,14 +462,16 @@ pub struct StSpecialFeature {
     #[serde(rename = "MainFeature", skip_serializing_if = "Option::is_none")]
     pub sf_main_feature: Option<String>,
     #[serde(rename = "ChangeState", skip_serializing_if = "Option::is_none")]
-    pub co_change_state: Option<String>,
+    pub co_change_state: Option<ChangeState>,
+    #[serde(rename = "ChangeTime", skip_serializing_if = "Option::is_none")]
+    pub co_change_time: Option<Typed<NaiveDateTime>>,
     #[serde(rename = "FeatureCode")]
-    pub sf_feature_code: String,
+    pub sf_feature_code: FeatureCode,
     #[serde(rename = "FeatureAdditionalCode", skip_serializing_if = "Option::is_none")]
     pub sf_feature_additional_code: Option<String>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OpOperations {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -339,7 +479,7 @@ pub struct OpOperations {
     pub op_operation: Vec<OpOperation>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OpOperation {
     #[serde(rename = "@mainType")]
     pub main_type: String,
@@ -347,20 +487,22 @@ pub struct OpOperation {
     pub id: String,
     #[serde(rename = "$text")]
     pub text: Option<String>,
-    #[serde(rename = "OperationInfo", skip_serializing_if = "Option::is_none")]
-    pub op_operation_info: Option<String>,
+    // The elements are written in the order of the Metsäkeskus exports, which
+    // the schema requires
     #[serde(rename = "ChangeState", skip_serializing_if = "Option::is_none")]
-    pub co_change_state: Option<String>,
+    pub co_change_state: Option<ChangeState>,
     #[serde(rename = "ChangeTime", skip_serializing_if = "Option::is_none")]
-    pub co_change_time: Option<String>,
+    pub co_change_time: Option<Typed<NaiveDateTime>>,
     #[serde(rename = "OperationType")]
-    pub op_operation_type: String,
+    pub op_operation_type: OperationType,
+    #[serde(rename = "ProposalData", skip_serializing_if = "Option::is_none")]
+    pub op_proposal_data: Option<OpProposalData>,
     #[serde(rename = "CompletionData", skip_serializing_if = "Option::is_none")]
     pub op_completion_data: Option<OpCompletionData>,
     #[serde(rename = "DataSource", skip_serializing_if = "Option::is_none")]
     pub co_data_source: Option<String>,
-    #[serde(rename = "ProposalData", skip_serializing_if = "Option::is_none")]
-    pub op_proposal_data: Option<OpProposalData>,
+    #[serde(rename = "OperationInfo", skip_serializing_if = "Option::is_none")]
+    pub op_operation_info: Option<String>,
     #[serde(rename = "Specifications", skip_serializing_if = "Option::is_none")]
     pub op_specifications: Option<OpSpecifications>,
     #[serde(rename = "Cutting", skip_serializing_if = "Option::is_none")]
@@ -369,15 +511,15 @@ pub struct OpOperation {
     pub op_silviculture: Option<OpSilviculture>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OpCompletionData {
     #[serde(rename = "$text")]
     pub text: Option<String>,
     #[serde(rename = "CompletionDate")]
-    pub op_completion_date: String
+    pub op_completion_date: Typed<NaiveDate>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OpSpecifications {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -385,43 +527,45 @@ pub struct OpSpecifications {
     pub op_specification: Vec<OpSpecification>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OpSpecification {
     #[serde(rename = "@id")]
     pub id: String,
     #[serde(rename = "$text")]
     pub text: Option<String>,
     #[serde(rename = "ChangeState")]
-    pub co_change_state: String,
+    pub co_change_state: ChangeState,
     #[serde(rename = "SpecificationCode")]
     pub op_specification_code: String
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OpSilviculture {
+    #[serde(rename = "$text")]
+    pub text: Option<String>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OpProposalData {
     #[serde(rename = "$text")]
     pub text: Option<String>,
     #[serde(rename = "ProposalType")]
-    pub op_proposal_type: String,
+    pub op_proposal_type: ProposalType,
     #[serde(rename = "ProposalYear")]
-    pub op_proposal_year: String
+    pub op_proposal_year: Typed<u32>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OpCutting {
     #[serde(rename = "$text")]
     pub text: Option<String>,
     #[serde(rename = "CuttingVolume", skip_serializing_if = "Option::is_none")]
-    pub op_cutting_volume: Option<String>,
+    pub op_cutting_volume: Option<Typed<f64>>,
     #[serde(rename = "Assortments", skip_serializing_if = "Option::is_none")]
     pub op_assortments: Option<OpAssortments>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OpAssortments {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -429,25 +573,27 @@ pub struct OpAssortments {
     pub op_assortment: Vec<OpAssortment>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OpAssortment {
     #[serde(rename = "@id")]
     pub id: String,
     #[serde(rename = "$text")]
     pub text: Option<String>,
     #[serde(rename = "ChangeState", skip_serializing_if = "Option::is_none")]
-    pub co_change_state: Option<String>,
+    pub co_change_state: Option<ChangeState>,
+    #[serde(rename = "ChangeTime", skip_serializing_if = "Option::is_none")]
+    pub co_change_time: Option<Typed<NaiveDateTime>>,
     #[serde(rename = "TreeSpecies")]
-    pub op_tree_species: String,
+    pub op_tree_species: TreeSpecies,
     #[serde(rename = "StemType")]
-    pub op_stem_type: String,
+    pub op_stem_type: StemType,
     #[serde(rename = "AssortmentVolume", skip_serializing_if = "Option::is_none")]
-    pub op_assortment_volume: Option<String>,
+    pub op_assortment_volume: Option<Typed<f64>>,
     #[serde(rename = "AssortmentPercent", skip_serializing_if = "Option::is_none")]
-    pub op_assortment_percent: Option<String>
+    pub op_assortment_percent: Option<Typed<f64>>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct TsTreeStandData {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -455,12 +601,12 @@ pub struct TsTreeStandData {
     pub ts_tree_stand_data_date: Vec<TsTreeStandDataDate>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct TsTreeStandDataDate {
     #[serde(rename = "@date")]
-    pub date: String,
+    pub date: Typed<NaiveDate>,
     #[serde(rename = "@type")]
-    pub ts_tree_stand_data_date_type: String,
+    pub ts_tree_stand_data_date_type: TreeStandDataType,
     #[serde(rename = "$text")]
     pub text: Option<String>,
     #[serde(rename = "TreeStrata", skip_serializing_if = "Option::is_none")]
@@ -471,7 +617,7 @@ pub struct TsTreeStandDataDate {
     pub tss_tree_stand_summary: Option<TssTreeStandSummary>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct DtsDeadTreeStrata {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -479,25 +625,27 @@ pub struct DtsDeadTreeStrata {
     pub dts_dead_tree_stratum: Vec<DtsDeadTreeStratum>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct DtsDeadTreeStratum {
     #[serde(rename = "@id")]
     pub id: String,
     #[serde(rename = "$text")]
     pub text: Option<String>,
     #[serde(rename = "ChangeState", skip_serializing_if = "Option::is_none")]
-    pub co_change_state: Option<String>,
+    pub co_change_state: Option<ChangeState>,
+    #[serde(rename = "ChangeTime", skip_serializing_if = "Option::is_none")]
+    pub co_change_time: Option<Typed<NaiveDateTime>>,
     #[serde(rename = "DeadTreeType")]
     pub dts_dead_tree_type: String,
     #[serde(rename = "TreeSpecies")]
-    pub dts_tree_species: String,
+    pub dts_tree_species: TreeSpecies,
     #[serde(rename = "MeanDiameter", skip_serializing_if = "Option::is_none")]
-    pub dts_mean_diameter: Option<String>,
+    pub dts_mean_diameter: Option<Typed<f64>>,
     #[serde(rename = "Volume", skip_serializing_if = "Option::is_none")]
-    pub dts_volume: Option<String>
+    pub dts_volume: Option<Typed<f64>>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct TstTreeStrata {
     #[serde(rename = "$text")]
     pub text: Option<String>,
@@ -505,93 +653,97 @@ pub struct TstTreeStrata {
     pub tst_tree_stratum: Vec<TstTreeStratum>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct TstTreeStratum {
     #[serde(rename = "@id")]
     pub id: String,
     #[serde(rename = "$text")]
     pub text: Option<String>,
     #[serde(rename = "ChangeState", skip_serializing_if = "Option::is_none")]
-    pub co_change_state: Option<String>,
+    pub co_change_state: Option<ChangeState>,
+    #[serde(rename = "ChangeTime", skip_serializing_if = "Option::is_none")]
+    pub co_change_time: Option<Typed<NaiveDateTime>>,
     #[serde(rename = "StratumNumber")]
-    pub tst_stratum_number: String,
+    pub tst_stratum_number: Typed<u32>,
     #[serde(rename = "TreeSpecies")]
-    pub tst_tree_species: String,
+    pub tst_tree_species: TreeSpecies,
     #[serde(rename = "Storey")]
     pub tst_storey: String,
     #[serde(rename = "Age")]
-    pub tst_age: String,
+    pub tst_age: Typed<u32>,
     #[serde(rename = "BasalArea", skip_serializing_if = "Option::is_none")]
-    pub tst_basal_area: Option<String>,
+    pub tst_basal_area: Option<Typed<f64>>,
     #[serde(rename = "StemCount", skip_serializing_if = "Option::is_none")]
-    pub tst_stem_count: Option<String>,
+    pub tst_stem_count: Option<Typed<u32>>,
     #[serde(rename = "MeanDiameter", skip_serializing_if = "Option::is_none")]
-    pub tst_mean_diameter: Option<String>,
+    pub tst_mean_diameter: Option<Typed<f64>>,
     #[serde(rename = "MeanHeight")]
-    pub tst_mean_height: String,
+    pub tst_mean_height: Typed<f64>,
     #[serde(rename = "Volume", skip_serializing_if = "Option::is_none")]
-    pub tst_volume: Option<String>,
+    pub tst_volume: Option<Typed<f64>>,
     #[serde(rename = "SawLogPercent", skip_serializing_if = "Option::is_none")]
-    pub tst_saw_log_percent: Option<String>,
+    pub tst_saw_log_percent: Option<Typed<f64>>,
     #[serde(rename = "SawLogVolume", skip_serializing_if = "Option::is_none")]
-    pub tst_saw_log_volume: Option<String>,
+    pub tst_saw_log_volume: Option<Typed<f64>>,
     #[serde(rename = "PulpWoodVolume", skip_serializing_if = "Option::is_none")]
-    pub tst_pulp_wood_volume: Option<String>,
+    pub tst_pulp_wood_volume: Option<Typed<f64>>,
     #[serde(rename = "VolumeGrowth", skip_serializing_if = "Option::is_none")]
-    pub tst_volume_growth: Option<String>,
+    pub tst_volume_growth: Option<Typed<f64>>,
     #[serde(rename = "DataSource", skip_serializing_if = "Option::is_none")]
     pub co_data_source: Option<String>,
     #[serde(rename = "LeafBiomass", skip_serializing_if = "Option::is_none")]
-    pub tst_leaf_biomass: Option<String>,
+    pub tst_leaf_biomass: Option<Typed<f64>>,
     #[serde(rename = "BranchBiomass", skip_serializing_if = "Option::is_none")]
-    pub tst_branch_biomass: Option<String>,
+    pub tst_branch_biomass: Option<Typed<f64>>,
     #[serde(rename = "StemBiomass", skip_serializing_if = "Option::is_none")]
-    pub tst_stem_biomass: Option<String>,
+    pub tst_stem_biomass: Option<Typed<f64>>,
     #[serde(rename = "StumpBiomass", skip_serializing_if = "Option::is_none")]
-    pub tst_stump_biomass: Option<String>
+    pub tst_stump_biomass: Option<Typed<f64>>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct TssTreeStandSummary {
     #[serde(rename = "@id")]
     pub id: String,
     #[serde(rename = "$text")]
     pub text: Option<String>,
     #[serde(rename = "ChangeState", skip_serializing_if = "Option::is_none")]
-    pub co_change_state: Option<String>,
+    pub co_change_state: Option<ChangeState>,
+    #[serde(rename = "ChangeTime", skip_serializing_if = "Option::is_none")]
+    pub co_change_time: Option<Typed<NaiveDateTime>>,
     #[serde(rename = "MeanAge")]
-    pub tss_mean_age: String,
+    pub tss_mean_age: Typed<u32>,
     #[serde(rename = "BasalArea")]
-    pub tss_basal_area: String,
+    pub tss_basal_area: Typed<f64>,
     #[serde(rename = "StemCount")]
-    pub tss_stem_count: String,
+    pub tss_stem_count: Typed<u32>,
     #[serde(rename = "MeanDiameter")]
-    pub tss_mean_diameter: String,
+    pub tss_mean_diameter: Typed<f64>,
     #[serde(rename = "MeanHeight")]
-    pub tss_mean_height: String,
+    pub tss_mean_height: Typed<f64>,
     #[serde(rename = "Volume")]
-    pub tss_volume: String,
+    pub tss_volume: Typed<f64>,
     #[serde(rename = "SawLogVolume", skip_serializing_if = "Option::is_none")]
-    pub tss_saw_log_volume: Option<String>,
+    pub tss_saw_log_volume: Option<Typed<f64>>,
     #[serde(rename = "PulpWoodVolume", skip_serializing_if = "Option::is_none")]
-    pub tss_pulp_wood_volume: Option<String>,
+    pub tss_pulp_wood_volume: Option<Typed<f64>>,
     #[serde(rename = "VolumeGrowth")]
-    pub tss_volume_growth: String,
+    pub tss_volume_growth: Typed<f64>,
     #[serde(rename = "Value", skip_serializing_if = "Option::is_none")]
-    pub tss_value: Option<String>,
+    pub tss_value: Option<Typed<f64>>,
     #[serde(rename = "ValueGrowthPercent", skip_serializing_if = "Option::is_none")]
-    pub tss_value_growth_percent: Option<String>,
+    pub tss_value_growth_percent: Option<Typed<f64>>,
     #[serde(rename = "DevelopmentClass", skip_serializing_if = "Option::is_none")]
-    pub tss_development_class: Option<String>,
+    pub tss_development_class: Option<DevelopmentClass>,
     #[serde(rename = "LeafBiomass", skip_serializing_if = "Option::is_none")]
-    pub tss_leaf_biomass: Option<String>,
+    pub tss_leaf_biomass: Option<Typed<f64>>,
     #[serde(rename = "BranchBiomass", skip_serializing_if = "Option::is_none")]
-    pub tss_branch_biomass: Option<String>,
+    pub tss_branch_biomass: Option<Typed<f64>>,
     #[serde(rename = "StemBiomass", skip_serializing_if = "Option::is_none")]
-    pub tss_stem_biomass: Option<String>,
+    pub tss_stem_biomass: Option<Typed<f64>>,
     #[serde(rename = "StumpBiomass", skip_serializing_if = "Option::is_none")]
-    pub tss_stump_biomass: Option<String>,
+    pub tss_stump_biomass: Option<Typed<f64>>,
     #[serde(rename = "MainTreeSpecies", skip_serializing_if = "Option::is_none")]
-    pub tss_main_tree_species: Option<String>
+    pub tss_main_tree_species: Option<TreeSpecies>
 }
 
@@ -0,0 +1,77 @@
+use geo_types::{LineString, Polygon};
+use serde_json::{json, Map, Value};
+use crate::error::ForestDataError;
+use crate::forest_property_data::{ForestPropertyData, StStand};
+use crate::projection::Crs;
+
+// The crs member is not part of RFC 7946 but QGIS and GDAL use it to pick
+// the right projection for TM35FIN coordinates
+const TM35FIN_CRS: &str = "urn:ogc:def:crs:EPSG::3067";
+
+impl ForestPropertyData {
+    // A GeoJSON FeatureCollection with one Feature per stand, in TM35FIN
+    pub fn to_geojson(&self) -> Result<Value, ForestDataError> {
+        self.to_geojson_in(Crs::Tm35Fin)
+    }
+
+    // A GeoJSON FeatureCollection with the stand polygons reprojected to `crs`.
+    // WGS84 output has no crs member, as RFC 7946 requires.
+    pub fn to_geojson_in(&self, crs: Crs) -> Result<Value, ForestDataError> {
+        let features = self.stands()
+            .map(|stand| stand.to_geojson_feature(crs))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut collection = json!({ "type": "FeatureCollection", "features": features });
+        if crs == Crs::Tm35Fin {
+            collection["crs"] = json!({ "type": "name", "properties": { "name": TM35FIN_CRS } });
+        }
+        Ok(collection)
+    }
+}
+
+impl StStand {
+    pub fn to_geojson_feature(&self, crs: Crs) -> Result<Value, ForestDataError> {
+        Ok(json!({
+            "type": "Feature",
+            "id": self.id,
+            "geometry": polygon_geometry(&self.polygon_in(crs)?),
+            "properties": self.geojson_properties(),
+        }))
+    }
+
+    fn geojson_properties(&self) -> Map<String, Value> {
+        let basic_data = &self.st_stand_basic_data;
+        let mut properties = Map::new();
+
+        properties.insert("stand_id".into(), json!(self.id));
+        properties.insert("stand_number".into(), json!(basic_data.st_stand_number));
+        properties.insert("stand_number_extension".into(), json!(basic_data.st_stand_number_extension));
+        properties.insert("area".into(), json!(basic_data.st_area.get()));
+        properties.insert("area_decrease".into(), json!(basic_data.st_area_decrease.as_ref().map(|a| a.get())));
+        properties.insert("main_group".into(), json!(basic_data.st_main_group.code()));
+        properties.insert("fertility_class".into(), json!(basic_data.st_fertility_class.as_ref().map(|c| c.code())));
+        properties.insert("main_tree_species".into(), json!(basic_data.st_main_tree_species.as_ref().map(|s| s.code())));
+
+        let data = self.latest_tree_stand_data();
+        let summary = data.and_then(|data| data.tss_tree_stand_summary.as_ref());
+        properties.insert("tree_stand_data_date".into(), json!(data.map(|data| data.date.text())));
+        properties.insert("tree_stand_data_type".into(), json!(data.map(|data| &data.ts_tree_stand_data_date_type)));
+        properties.insert("volume".into(), json!(summary.map(|s| s.tss_volume.get())));
+        properties.insert("mean_age".into(), json!(summary.map(|s| s.tss_mean_age.get())));
+
+        properties
+    }
+}
+
+fn polygon_geometry(polygon: &Polygon<f64>) -> Value {
+    let rings: Vec<Value> = std::iter::once(polygon.exterior())
+        .chain(polygon.interiors())
+        .map(ring_coordinates)
+        .collect();
+
+    json!({ "type": "Polygon", "coordinates": rings })
+}
+
+fn ring_coordinates(ring: &LineString<f64>) -> Value {
+    Value::Array(ring.coords().map(|c| json!([c.x, c.y])).collect())
+}
@@ -0,0 +1,162 @@
+use geo::Area;
+use geo_types::{Coord, LineString, MultiPolygon, Point, Polygon};
+use crate::error::ForestDataError;
+use crate::forest_property_data::{ForestPropertyData, ReParcel, ReRealEstate, StStand, StStands};
+use crate::projection::Crs;
+
+const SQUARE_METRES_PER_HECTARE: f64 = 10_000.0;
+
+// How far the computed area may be from the registered area before a stand is flagged
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AreaTolerance {
+    Hectares(f64),
+    // Fraction of the registered area, e.g. 0.05 for 5 %
+    Relative(f64),
+}
+
+impl Default for AreaTolerance {
+    fn default() -> Self {
+        AreaTolerance::Hectares(0.1)
+    }
+}
+
+// A stand whose polygon area differs from Area minus AreaDecrease by more than the tolerance
+#[derive(Debug, Clone, PartialEq)]
+pub struct AreaMismatch {
+    pub stand_id: String,
+    pub stand_number: String,
+    pub registered_ha: f64,
+    pub computed_ha: f64,
+}
+
+impl AreaMismatch {
+    pub fn difference_ha(&self) -> f64 {
+        self.computed_ha - self.registered_ha
+    }
+}
+
+// Parses a GML coordinate string: tuples separated by whitespace,
+// x and y within a tuple separated by a comma
+pub fn parse_coordinates(text: &str) -> Result<Vec<Coord<f64>>, ForestDataError> {
+    text.split_whitespace()
+        .map(|tuple| {
+            let mut values = tuple.split(',').map(|v| v.parse::<f64>());
+            match (values.next(), values.next()) {
+                (Some(Ok(x)), Some(Ok(y))) => Ok(Coord { x, y }),
+                _ => Err(ForestDataError::Geometry(format!("Invalid coordinate tuple `{}`", tuple))),
+            }
+        })
+        .collect()
+}
+
+// Formats coordinates as a GML coordinate string
+pub fn format_coordinates(coords: &[Coord<f64>], decimals: usize) -> String {
+    coords.iter()
+        .map(|c| format!("{:.*},{:.*}", decimals, c.x, decimals, c.y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_ring(text: &str, stand_id: &str) -> Result<LineString<f64>, ForestDataError> {
+    let coords = parse_coordinates(text)?;
+    if coords.len() < 3 {
+        return Err(ForestDataError::Geometry(format!("Stand {}: a ring needs at least 3 coordinates", stand_id)));
+    }
+    Ok(LineString::from(coords))
+}
+
+impl StStand {
+    // The stand polygon with its exterior and interior rings
+    pub fn polygon(&self) -> Result<Polygon<f64>, ForestDataError> {
+        let polygon = &self.st_stand_basic_data.gdt_polygon_geometry.gml_polygon_property.gml_polygon;
+
+        let exterior = polygon.gml_exterior.as_ref()
+            .ok_or_else(|| ForestDataError::Geometry(format!("Stand {}: polygon has no exterior ring", self.id)))?;
+        let exterior = parse_ring(&exterior.gml_linear_ring.gml_coordinates, &self.id)?;
+
+        let interiors = polygon.gml_interior.iter().flatten()
+            .map(|interior| parse_ring(&interior.gml_linear_ring.gml_coordinates, &self.id))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Polygon::new(exterior, interiors))
+    }
+
+    // Planar area of the polygon in hectares, computed in TM35FIN
+    pub fn computed_area_ha(&self) -> Result<f64, ForestDataError> {
+        Ok(self.polygon_in(Crs::Tm35Fin)?.unsigned_area() / SQUARE_METRES_PER_HECTARE)
+    }
+
+    // Area minus AreaDecrease in hectares
+    pub fn registered_area_ha(&self) -> f64 {
+        let basic_data = &self.st_stand_basic_data;
+        basic_data.st_area.get() - basic_data.st_area_decrease.as_ref().map_or(0.0, |d| d.get())
+    }
+
+    pub fn check_area(&self, tolerance: AreaTolerance) -> Result<Option<AreaMismatch>, ForestDataError> {
+        let registered_ha = self.registered_area_ha();
+        let computed_ha = self.computed_area_ha()?;
+        let allowed = match tolerance {
+            AreaTolerance::Hectares(ha) => ha,
+            AreaTolerance::Relative(fraction) => fraction * registered_ha.abs(),
+        };
+
+        if (computed_ha - registered_ha).abs() <= allowed {
+            return Ok(None);
+        }
+
+        Ok(Some(AreaMismatch {
+            stand_id: self.id.clone(),
+            stand_number: self.st_stand_basic_data.st_stand_number.clone(),
+            registered_ha,
+            computed_ha,
+        }))
+    }
+
+    // The representative point given in the stand's pointProperty
+    pub fn centroid_point(&self) -> Result<Point<f64>, ForestDataError> {
+        let point = &self.st_stand_basic_data.gdt_polygon_geometry.gml_point_property.gml_point;
+
+        match parse_coordinates(&point.gml_coordinates)?.as_slice() {
+            [coord] => Ok(Point::from(*coord)),
+            _ => Err(ForestDataError::Geometry(format!("Stand {}: point must have exactly one coordinate", self.id))),
+        }
+    }
+}
+
+impl StStands {
+    pub fn multi_polygon(&self) -> Result<MultiPolygon<f64>, ForestDataError> {
+        let polygons = self.st_stand.iter()
+            .map(|stand| stand.polygon())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(MultiPolygon::new(polygons))
+    }
+}
+
+impl ReParcel {
+    // The polygons of all stands on the parcel
+    pub fn multi_polygon(&self) -> Result<MultiPolygon<f64>, ForestDataError> {
+        self.st_stands.multi_polygon()
+    }
+}
+
+impl ReRealEstate {
+    // The polygons of all stands on all parcels of the estate
+    pub fn multi_polygon(&self) -> Result<MultiPolygon<f64>, ForestDataError> {
+        let mut polygons = Vec::new();
+        for parcel in &self.re_parcels.re_parcel {
+            polygons.extend(parcel.multi_polygon()?);
+        }
+        Ok(MultiPolygon::new(polygons))
+    }
+}
+
+impl ForestPropertyData {
+    // Compares the polygon area of every stand with its registered area
+    pub fn check_areas(&self, tolerance: AreaTolerance) -> Result<Vec<AreaMismatch>, ForestDataError> {
+        let mut mismatches = Vec::new();
+        for stand in self.stands() {
+            mismatches.extend(stand.check_area(tolerance)?);
+        }
+        Ok(mismatches)
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt;
+use serde::Serialize;
+use crate::codes::{OperationType, StemType, TreeSpecies};
+use crate::forest_property_data::{ForestPropertyData, OpCutting, OpOperation, StStand};
+
+// Removal of one tree species and timber assortment in m³, or m³/ha for the
+// assortments of a single cutting
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AssortmentRemoval {
+    pub species: TreeSpecies,
+    pub stem_type: StemType,
+    pub volume: f64,
+}
+
+// The proposed cuttings of one operation type in one year
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HarvestGroup {
+    pub year: u32,
+    pub operation_type: OperationType,
+    pub operation_count: usize,
+    pub stand_ids: Vec<String>,
+    // Area of the stands in `stand_ids`
+    pub area_ha: f64,
+    // Total removal in m³
+    pub volume: f64,
+    // Ordered by tree species and stem type code
+    pub assortments: Vec<AssortmentRemoval>,
+}
+
+// Proposed cuttings grouped by proposal year and operation type
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct HarvestPlan {
+    // Ordered by year and operation type code
+    pub groups: Vec<HarvestGroup>,
+}
+
+impl HarvestPlan {
+    pub fn from_stands<'a>(stands: impl IntoIterator<Item = &'a StStand>) -> HarvestPlan {
+        let mut plan = HarvestPlan::default();
+        for stand in stands {
+            for operation in stand.op_operations.iter().flat_map(|operations| &operations.op_operation) {
+                plan.add_operation(stand, operation);
+            }
+        }
+        plan.groups.sort_by_key(|group| (group.year, code_order(group.operation_type.code())));
+        for group in &mut plan.groups {
+            group.assortments.sort_by_key(|removal| (code_order(removal.species.code()), code_order(removal.stem_type.code())));
+        }
+        plan
+    }
+
+    fn add_operation(&mut self, stand: &StStand, operation: &OpOperation) {
+        let (Some(proposal), Some(cutting)) = (&operation.op_proposal_data, &operation.op_cutting) else {
+            return;
+        };
+        if operation.op_completion_data.is_some() {
+            return;
+        }
+
+        let year = proposal.op_proposal_year.get();
+        let index = match self.groups.iter().position(|group| group.year == year && group.operation_type == operation.op_operation_type) {
+            Some(index) => index,
+            None => {
+                self.groups.push(HarvestGroup {
+                    year,
+                    operation_type: operation.op_operation_type.clone(),
+                    operation_count: 0,
+                    stand_ids: Vec::new(),
+                    area_ha: 0.0,
+                    volume: 0.0,
+                    assortments: Vec::new(),
+                });
+                self.groups.len() - 1
+            }
+        };
+        let group = &mut self.groups[index];
+
+        let area_ha = stand.st_stand_basic_data.st_area.get();
+        group.operation_count += 1;
+        if !group.stand_ids.contains(&stand.id) {
+            group.stand_ids.push(stand.id.clone());
+            group.area_ha += area_ha;
+        }
+
+        let assortments = cutting.assortment_volumes();
+        group.volume += cutting.removal().unwrap_or(0.0) * area_ha;
+        for assortment in assortments {
+            match group.assortments.iter_mut().find(|removal| removal.species == assortment.species && removal.stem_type == assortment.stem_type) {
+                Some(removal) => removal.volume += assortment.volume * area_ha,
+                None => group.assortments.push(AssortmentRemoval { volume: assortment.volume * area_ha, ..assortment }),
+            }
+        }
+    }
+
+    pub fn volume(&self) -> f64 {
+        // Summing f64s starts from -0.0, which an empty plan would print as "-0.0"
+        self.groups.iter().fold(0.0, |total, group| total + group.volume)
+    }
+}
+
+impl fmt::Display for HarvestPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for group in &self.groups {
+            let operation_type = group.operation_type.english_name().unwrap_or(group.operation_type.code());
+            writeln!(
+                f,
+                "{} {}: {} stands, {:.2} ha, {:.1} m³",
+                group.year, operation_type, group.stand_ids.len(), group.area_ha, group.volume
+            )?;
+            for removal in &group.assortments {
+                let species = removal.species.english_name().unwrap_or(removal.species.code());
+                let stem_type = removal.stem_type.english_name().unwrap_or(removal.stem_type.code());
+                writeln!(f, "  {:<28} {:<10} {:>10.1}", species, stem_type, removal.volume)?;
+            }
+        }
+        write!(f, "Total: {:.1} m³", self.volume())
+    }
+}
+
+impl OpCutting {
+    // The assortments in m³/ha. AssortmentPercent is resolved against
+    // CuttingVolume when an assortment has no AssortmentVolume; assortments
+    // that have neither are left out.
+    pub fn assortment_volumes(&self) -> Vec<AssortmentRemoval> {
+        let cutting_volume = self.op_cutting_volume.as_ref().map(|volume| volume.get());
+        self.op_assortments.iter()
+            .flat_map(|assortments| &assortments.op_assortment)
+            .filter_map(|assortment| {
+                let volume = match (&assortment.op_assortment_volume, &assortment.op_assortment_percent, cutting_volume) {
+                    (Some(volume), _, _) => volume.get(),
+                    (None, Some(percent), Some(cutting_volume)) => cutting_volume * percent.get() / 100.0,
+                    _ => return None,
+                };
+                Some(AssortmentRemoval {
+                    species: assortment.op_tree_species.clone(),
+                    stem_type: assortment.op_stem_type.clone(),
+                    volume,
+                })
+            })
+            .collect()
+    }
+
+    // Removal in m³/ha, the CuttingVolume or else the sum of the assortments
+    pub fn removal(&self) -> Option<f64> {
+        match &self.op_cutting_volume {
+            Some(volume) => Some(volume.get()),
+            None => self.assortment_volumes().iter().map(|removal| removal.volume).reduce(|a, b| a + b),
+        }
+    }
+}
+
+// Numeric codes in order, unlisted codes after them
+fn code_order(code: &str) -> (u32, String) {
+    (code.parse().unwrap_or(u32::MAX), code.to_string())
+}
+
+impl StStand {
+    pub fn harvest_plan(&self) -> HarvestPlan {
+        HarvestPlan::from_stands([self])
+    }
+}
+
+impl ForestPropertyData {
+    // The proposed cuttings of every stand in the document. Operations with
+    // CompletionData have already been carried out and are left out.
+    pub fn harvest_plan(&self) -> HarvestPlan {
+        HarvestPlan::from_stands(self.stands())
+    }
+}
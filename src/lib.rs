@@ -1,2 +1,27 @@
+pub mod batch;
+pub mod carbon;
+pub mod change_set;
+pub mod codes;
+pub mod compare;
+pub mod diff;
+pub mod encoding;
+pub mod error;
 pub mod forest_property_data;
-pub mod forest_property_data_namespaces;
+pub mod geojson;
+pub mod geometry;
+pub mod harvest;
+pub mod merge;
+pub mod namespaces;
+pub mod projection;
+pub mod spatial;
+pub mod split;
+pub mod stand_summary;
+pub mod timeseries;
+pub mod valuation;
+pub mod values;
+pub mod volumes;
+pub mod writer;
+
+pub use error::{ForestDataError, XmlError};
+pub use namespaces::{Namespace, PrefixStyle};
+pub use writer::XmlWriteOptions;
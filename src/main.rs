@@ -1,163 +1,520 @@
+use forestry_xml_parser::batch::{convert_directory, BatchFormat, BatchOptions};
+use forestry_xml_parser::compare::compare_xml;
+use forestry_xml_parser::encoding::{decode_xml, read_xml_file};
 use forestry_xml_parser::forest_property_data::ForestPropertyData;
-use serde_json;
-use std::collections::HashMap;
-use std::fs::File;
+use forestry_xml_parser::carbon::CarbonFactors;
+use forestry_xml_parser::diff::diff;
+use forestry_xml_parser::geometry::AreaTolerance;
+use forestry_xml_parser::split::{file_names, SplitLevel};
+use forestry_xml_parser::stand_summary::SummaryTolerance;
+use forestry_xml_parser::valuation::PriceTable;
+use forestry_xml_parser::volumes::VolumeBreakdown;
+use forestry_xml_parser::{ForestDataError, PrefixStyle, XmlWriteOptions};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::fs;
 use std::io::{Read, Write};
-use quick_xml::se::to_string;
-use xmlem::Document;
-use std::str::FromStr;
-use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 use regex::Regex;
-use std::io::{self, BufRead};
-use std::path::Path;
-
-fn main() {
-    let source_url = "https://avoin.metsakeskus.fi/rest/mvrest/FRStandData/v1/ByPolygon?wktPolygon=POLYGON%20((393960.156%206801453.126,%20394798.608%206801657.878,%20394930.512%206801670.111,%20395028.723%206802116.858,%20394258.945%206801929.148,%20394261.711%206801810.541,%20394091.166%206801665.961,%20393960.156%206801453.126))&stdVersion=MV1.9";
-    //let source_file_name = "xml_history/XML_MV_K3414E.xml";
-    //let source_file_name = "xml_stands/XML_MV_R5311E.xml";
-    let source_file_name = "orig_forestpropertydata.xml";
-    
-    // Create a JSON file from the XML data
-    let json_file_name = create_json_file(source_file_name);
-
-    // Convert the JSON file back to XML
-    let info_lines = information_lines(source_file_name);
-    json_to_xml_xmlem(&json_file_name, &info_lines);
-}
-
-// Create a JSON file from the XML data
-// Returns the name of the JSON file
-fn create_json_file(input_string: &str) -> String {
-    let property: ForestPropertyData;
-    let json_file_name;
-    let mut standard = "".to_string();
-
-    if input_string.starts_with("https://") || input_string.starts_with("http://") {
-        property = ForestPropertyData::from_xml_url(input_string);
-        json_file_name = "forestpropertydata_url.json".to_string();
-    } else {
-        let xml_string = read_file_without_bom(input_string).expect("Could not read file");
-        property = ForestPropertyData::from_xml_str(&xml_string);
 
-        if let Some(std) = get_standard(&xml_string) {
-            standard = std;
-        }
-        json_file_name = format!("forestpropertydata_file_{}.json", standard);
-    }
+/// Converts and inspects Metsäkeskus forest property data (ForestPropertyData XML)
+#[derive(Parser)]
+#[command(name = "forestry_xml_parser", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
 
-    match serde_json::to_string_pretty(&property) {
-        Ok(json) => save_to_file(&json_file_name, &json),
-        Err(e) => println!("Error: {}", e),
-    }
+#[derive(Subcommand)]
+enum Command {
+    /// Convert ForestPropertyData XML to JSON
+    ToJson {
+        /// Input XML file, or `-` for stdin
+        input: String,
+        #[command(flatten)]
+        output: OutputArgs,
+        /// Write a GeoJSON FeatureCollection of the stands instead
+        #[arg(long)]
+        geojson: bool,
+    },
+    /// Convert JSON written by `to-json` back to XML
+    ToXml {
+        /// Input JSON file, or `-` for stdin
+        input: String,
+        #[command(flatten)]
+        output: OutputArgs,
+        #[command(flatten)]
+        xml: XmlArgs,
+    },
+    /// Download ForestPropertyData from a URL, e.g. the Metsäkeskus REST API
+    Fetch {
+        url: String,
+        #[command(flatten)]
+        output: OutputArgs,
+        #[arg(long, value_enum, default_value_t = Format::Json)]
+        format: Format,
+        #[command(flatten)]
+        xml: XmlArgs,
+    },
+    /// Convert every XML file under a directory in parallel
+    Batch {
+        input_dir: PathBuf,
+        /// Write into a tree mirroring the input directory instead of next to the inputs.
+        /// Required with `--format xml`.
+        #[arg(long, required_if_eq("format", "xml"))]
+        output_dir: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = Format::Json)]
+        format: Format,
+        /// Write without indentation
+        #[arg(long)]
+        compact: bool,
+        /// Number of worker threads, all cores by default
+        #[arg(short, long)]
+        jobs: Option<usize>,
+        #[command(flatten)]
+        xml: XmlArgs,
+    },
+    /// Split an XML file into one document per real estate, parcel or stand
+    Split {
+        /// Input XML file, or `-` for stdin
+        input: String,
+        #[arg(long, value_enum, default_value_t = Level::Stand)]
+        by: Level,
+        /// Directory for the documents, named `<id>.xml`
+        #[arg(long)]
+        output_dir: PathBuf,
+        #[command(flatten)]
+        xml: XmlArgs,
+    },
+    /// Show the stands, strata, operations and special features that changed
+    /// between two versions of the same data. Exits with status 1 if they differ.
+    Diff {
+        /// The earlier version
+        before: String,
+        /// The later version
+        after: String,
+        /// Print the changes as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print an overview of an XML file
+    Summary {
+        /// Input XML file, or `-` for stdin
+        input: String,
+        /// Also print the growing stock table of each real estate, parcel or stand
+        #[arg(long, value_enum)]
+        volumes_by: Option<Level>,
+    },
+    /// Print the proposed cuttings by year and operation type, with the removal
+    /// per tree species and timber assortment
+    Harvest {
+        /// Input XML file, or `-` for stdin
+        input: String,
+        /// Print the plan as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Estimate the stumpage value of the proposed cuttings and the standing
+    /// timber value with a timber price table
+    Value {
+        /// Input XML file, or `-` for stdin
+        input: String,
+        /// Price table, TOML if the file name ends in `.toml`, otherwise JSON
+        #[arg(long)]
+        prices: String,
+        /// Also print the values of each stand
+        #[arg(long)]
+        by_stand: bool,
+        /// Print the values as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check that an XML file parses and round-trips without losing data
+    Validate {
+        /// Input XML file, or `-` for stdin
+        input: String,
+        /// Also compare stand polygon areas with Area minus AreaDecrease, tolerance in hectares
+        #[arg(long, value_name = "HECTARES")]
+        area_tolerance: Option<f64>,
+        /// Also compare each TreeStandSummary with the values derived from its tree strata
+        #[arg(long)]
+        summaries: bool,
+    },
+}
 
-    json_file_name
+#[derive(Args)]
+struct OutputArgs {
+    /// Output file, or `-` for stdout
+    #[arg(short, long, default_value = "-")]
+    output: String,
+    /// Write without indentation
+    #[arg(long)]
+    compact: bool,
 }
 
-// Convert a JSON file to XML using the xmlem crate
-fn json_to_xml_xmlem(file_name: &str, info_lines: &Option<Vec<String>>) {
-    let mut file = File::open(file_name).expect("Unable to open file");
-    let mut json_data = String::new();
-    file.read_to_string(&mut json_data).expect("Unable to read data");
+#[derive(Args)]
+struct XmlArgs {
+    #[arg(long, value_enum, default_value_t = Prefixes::Metsakeskus)]
+    prefixes: Prefixes,
+    /// Encoding of the written XML, e.g. utf-8 or iso-8859-1
+    #[arg(long, default_value = "utf-8")]
+    encoding: String,
+}
 
-    // Get the root tag from the JSON data
-    let json_value: Value = serde_json::from_str(&json_data).unwrap();
-    let root_tag = generate_xml_tag_from_json(&json_value);
-    
-    let forest_property_data: ForestPropertyData = serde_json::from_str(&json_data).expect("Could not parse JSON");
-    let xml_string = to_string(&forest_property_data).expect("Could not convert to XML");
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Json,
+    Geojson,
+    Xml,
+}
 
-    let doc = Document::from_str(&xml_string).unwrap();
-    let mut pretty_xml = doc.to_string_pretty();
+#[derive(Clone, Copy, ValueEnum)]
+enum Level {
+    Estate,
+    Parcel,
+    Stand,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Prefixes {
+    Metsakeskus,
+    Bare,
+}
 
-    let re_opening = Regex::new(r#"<ForestPropertyData[^>]*>"#).unwrap();
-    pretty_xml = re_opening.replace(&pretty_xml, "").to_string();
-    pretty_xml = root_tag.as_str().to_owned() + &pretty_xml;
+fn main() -> ExitCode {
+    let cli = Cli::parse();
 
-    // Add information about the XML parser
-    if let Some(lines) = info_lines {
-        let mut info_string: String = String::new();
+    let result = match cli.command {
+        Command::ToJson { input, output, geojson } => to_json(&input, &output, geojson),
+        Command::ToXml { input, output, xml } => to_xml(&input, &output, &xml),
+        Command::Fetch { url, output, format, xml } => fetch(&url, &output, format, &xml),
+        Command::Batch { input_dir, output_dir, format, compact, jobs, xml } => {
+            batch(input_dir, output_dir, format, compact, jobs, &xml)
+        }
+        Command::Split { input, by, output_dir, xml } => split(&input, by, &output_dir, &xml),
+        Command::Diff { before, after, json } => diff_files(&before, &after, json),
+        Command::Summary { input, volumes_by } => summary(&input, volumes_by),
+        Command::Harvest { input, json } => harvest(&input, json),
+        Command::Value { input, prices, by_stand, json } => value(&input, &prices, by_stand, json),
+        Command::Validate { input, area_tolerance, summaries } => validate(&input, area_tolerance, summaries),
+    };
 
-        for line in lines {
-            info_string = info_string.to_owned() + line + "\n";
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
         }
+    }
+}
+
+fn to_json(input: &str, output: &OutputArgs, geojson: bool) -> Result<bool, ForestDataError> {
+    let property = ForestPropertyData::try_from_xml_str(&read_xml_input(input)?)?;
 
-        pretty_xml = info_string + "<!--Parsed with forestry_xml_parser V0.1.0-->\n" + &pretty_xml;
+    let json = if geojson {
+        let geojson = property.to_geojson()?;
+        json_string(&geojson, output.compact)?
     } else {
-        pretty_xml = "<!--Parsed with forestry_xml_parser V0.1.0-->\n".to_owned() + &pretty_xml;
+        json_string(&property, output.compact)?
+    };
+
+    write_output(&output.output, json.as_bytes())?;
+    Ok(true)
+}
+
+fn to_xml(input: &str, output: &OutputArgs, xml: &XmlArgs) -> Result<bool, ForestDataError> {
+    let json = read_input(input)?;
+    let property: ForestPropertyData = serde_json::from_slice(&json)
+        .map_err(|e| ForestDataError::Serialization(e.to_string()))?;
+
+    write_output(&output.output, &property.to_xml(&xml_options(output, xml))?)?;
+    Ok(true)
+}
+
+fn fetch(url: &str, output: &OutputArgs, format: Format, xml: &XmlArgs) -> Result<bool, ForestDataError> {
+    let property = ForestPropertyData::try_from_xml_url(url)?;
+
+    match format {
+        Format::Json => write_output(&output.output, json_string(&property, output.compact)?.as_bytes())?,
+        Format::Geojson => write_output(&output.output, json_string(&property.to_geojson()?, output.compact)?.as_bytes())?,
+        Format::Xml => write_output(&output.output, &property.to_xml(&xml_options(output, xml))?)?,
+    }
+    Ok(true)
+}
+
+fn batch(
+    input_dir: PathBuf,
+    output_dir: Option<PathBuf>,
+    format: Format,
+    compact: bool,
+    jobs: Option<usize>,
+    xml: &XmlArgs,
+) -> Result<bool, ForestDataError> {
+    if let Some(jobs) = jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()
+            .map_err(|e| ForestDataError::Io(std::io::Error::other(e)))?;
     }
 
-    //add_prefixes(&mut pretty_xml);
+    let output = OutputArgs { output: "-".to_string(), compact };
+    let opts = BatchOptions {
+        format: match format {
+            Format::Json => BatchFormat::Json,
+            Format::Geojson => BatchFormat::GeoJson,
+            Format::Xml => BatchFormat::Xml,
+        },
+        output_dir,
+        pretty: !compact,
+        xml: xml_options(&output, xml),
+    };
+
+    let report = convert_directory(&input_dir, &opts)?;
+    print!("{}", report);
+    Ok(report.is_success())
+}
+
+fn split(input: &str, by: Level, output_dir: &Path, xml: &XmlArgs) -> Result<bool, ForestDataError> {
+    let property = ForestPropertyData::try_from_xml_str(&read_xml_input(input)?)?;
+    let level = match by {
+        Level::Estate => SplitLevel::RealEstate,
+        Level::Parcel => SplitLevel::Parcel,
+        Level::Stand => SplitLevel::Stand,
+    };
+    let opts = xml_options(&OutputArgs { output: "-".to_string(), compact: false }, xml);
+
+    fs::create_dir_all(output_dir)?;
+    let parts = property.split(level);
+    for (part, name) in parts.iter().zip(file_names(&parts)) {
+        if name != format!("{}.xml", part.file_stem()) {
+            eprintln!("Warning: id {} occurs more than once, written to {}", part.file_stem(), name);
+        }
+        part.data.write_xml_file(&output_dir.join(name).to_string_lossy(), &opts)?;
+    }
 
-    let new_file_name = file_name.replace(".json", ".xml");
-    save_to_file(&new_file_name, &pretty_xml);
+    println!("Wrote {} documents to {}", parts.len(), output_dir.display());
+    Ok(true)
 }
 
-fn save_to_file(file_name: &str, data: &str) {
-    let mut file = File::create(file_name).expect("Unable to create file");
-    file.write_all(data.as_bytes()).expect("Unable to write data");
+fn diff_files(before: &str, after: &str, json: bool) -> Result<bool, ForestDataError> {
+    let before = ForestPropertyData::try_from_xml_str(&read_xml_input(before)?)?;
+    let after = ForestPropertyData::try_from_xml_str(&read_xml_input(after)?)?;
+    let changes = diff(&before, &after)?;
+
+    if json {
+        println!("{}", json_string(&changes, false)?);
+    } else {
+        print!("{}", changes);
+    }
+    Ok(changes.is_empty())
 }
 
-fn information_lines(file_name: &str) -> Option<Vec<String>> {
-    let mut info_lines = Vec::new();
+fn summary(input: &str, volumes_by: Option<Level>) -> Result<bool, ForestDataError> {
+    let property = ForestPropertyData::try_from_xml_str(&read_xml_input(input)?)?;
+
+    let standard = property.header_comments.iter().find_map(|comment| get_standard(comment));
+    println!("Standard version: {}", standard.as_deref().unwrap_or("unknown"));
+
+    for estate in property.real_estates() {
+        println!("Real estate: {} ({}), {} parcels", estate.re_real_estate_name, estate.id, estate.re_parcels.re_parcel.len());
+    }
+
+    let stands: Vec<_> = property.stands().collect();
+    let area: f64 = stands.iter().map(|stand| stand.st_stand_basic_data.st_area.get()).sum();
+    let with_tree_data = stands.iter().filter(|stand| stand.ts_tree_stand_data.is_some()).count();
+    println!("Stands: {} ({} with tree stand data)", stands.len(), with_tree_data);
+    println!("Area: {:.2} ha", area);
+
+    let operations: Vec<_> = stands.iter()
+        .filter_map(|stand| stand.op_operations.as_ref())
+        .flat_map(|operations| &operations.op_operation)
+        .collect();
+    let cuttings = operations.iter().filter(|op| op.op_cutting.is_some()).count();
+    let silviculture = operations.iter().filter(|op| op.op_silviculture.is_some()).count();
+    println!("Operations: {} ({} cutting, {} silviculture)", operations.len(), cuttings, silviculture);
+
+    let carbon = property.carbon_stock(&CarbonFactors::default());
+    println!(
+        "Carbon: {:.1} t CO2 in {:.1} t of biomass ({:.1} t estimated from volume)",
+        carbon.co2, carbon.biomass, carbon.estimated_biomass
+    );
 
-    if let Ok(lines) = read_lines(file_name) {
-        for line in lines.flatten() {
-            if line.contains("ForestPropertyData") {
-                break;
+    println!();
+    print_volumes("Growing stock", &property.volume_breakdown());
+
+    match volumes_by {
+        Some(Level::Estate) => {
+            for estate in property.real_estates() {
+                print_volumes(&format!("Real estate {} ({})", estate.re_real_estate_name, estate.id), &estate.volume_breakdown());
             }
-            info_lines.push(line.clone());
         }
-    } else {
-        return None;
+        Some(Level::Parcel) => {
+            for parcel in property.real_estates().flat_map(|estate| &estate.re_parcels.re_parcel) {
+                print_volumes(&format!("Parcel {} ({})", parcel.re_parcel_number, parcel.id), &parcel.volume_breakdown());
+            }
+        }
+        Some(Level::Stand) => {
+            for stand in property.stands() {
+                print_volumes(&format!("Stand {} ({})", stand.st_stand_basic_data.st_stand_number, stand.id), &stand.volume_breakdown());
+            }
+        }
+        None => {}
     }
 
-    Some(info_lines)
+    Ok(true)
 }
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+fn print_volumes(title: &str, breakdown: &VolumeBreakdown) {
+    let per_ha = breakdown.per_hectare();
+    println!("{}, {:.2} ha", title, breakdown.area_ha);
+    println!(
+        "  {:<28} {:>10} {:>8} {:>10} {:>8} {:>10} {:>8}",
+        "Species", "Volume m³", "m³/ha", "Saw log", "m³/ha", "Pulp wood", "m³/ha"
+    );
+
+    let total = |name: &str, volumes: (f64, f64, f64), per_ha: (f64, f64, f64)| {
+        println!(
+            "  {:<28} {:>10.1} {:>8.1} {:>10.1} {:>8.1} {:>10.1} {:>8.1}",
+            name, volumes.0, per_ha.0, volumes.1, per_ha.1, volumes.2, per_ha.2
+        );
+    };
+    for (species, species_per_ha) in breakdown.species.iter().zip(&per_ha.species) {
+        let name = species.species.english_name().unwrap_or(species.species.code());
+        total(
+            name,
+            (species.volume, species.saw_log_volume, species.pulp_wood_volume),
+            (species_per_ha.volume, species_per_ha.saw_log_volume, species_per_ha.pulp_wood_volume),
+        );
+    }
+    total(
+        "Total",
+        (breakdown.volume(), breakdown.saw_log_volume(), breakdown.pulp_wood_volume()),
+        (per_ha.volume(), per_ha.saw_log_volume(), per_ha.pulp_wood_volume()),
+    );
 }
 
-fn generate_xml_tag_from_json(json: &Value) -> String {
-    let mut attributes = String::new();
+fn harvest(input: &str, json: bool) -> Result<bool, ForestDataError> {
+    let property = ForestPropertyData::try_from_xml_str(&read_xml_input(input)?)?;
+    let plan = property.harvest_plan();
 
-    match json {
-        Value::Object(map) => {
-            for (key, value) in map {
-                // If the key starts with '@', treat it as an XML attribute
-                if key.starts_with('@') {
-                    let attribute_name = key.trim_start_matches('@'); // Remove the '@' prefix
-                    attributes.push_str(&format!(r#" {}={}"#, attribute_name, value));
-                }
-            }
+    if json {
+        println!("{}", json_string(&plan, false)?);
+    } else {
+        println!("{}", plan);
+    }
+    Ok(true)
+}
+
+fn value(input: &str, prices: &str, by_stand: bool, json: bool) -> Result<bool, ForestDataError> {
+    let property = ForestPropertyData::try_from_xml_str(&read_xml_input(input)?)?;
+    let value = property.value(&PriceTable::from_file(prices)?);
+
+    if json {
+        println!("{}", json_string(&value, false)?);
+        return Ok(true);
+    }
+
+    if by_stand {
+        for stand in &value.stands {
+            println!(
+                "Stand {}: {:.2} ha, standing {:.0} €, cuttings {:.0} €",
+                stand.stand_id, stand.area_ha, stand.standing_value, stand.cutting_value()
+            );
         }
-        // If the JSON is a string, just use it as the text content
-        Value::String(s) => {
-            attributes.push_str(s);
+        println!();
+    }
+    println!("Area: {:.2} ha", value.area_ha());
+    println!("Standing timber: {:.0} €", value.standing_value());
+    println!("Proposed cuttings: {:.0} €", value.cutting_value());
+    for (year, cutting_value) in value.cutting_value_by_year() {
+        println!("  {}: {:.0} €", year, cutting_value);
+    }
+    if value.unpriced_volume() > 0.0 {
+        println!("Volume without a price: {:.1} m³", value.unpriced_volume());
+    }
+    Ok(true)
+}
+
+fn validate(input: &str, area_tolerance: Option<f64>, summaries: bool) -> Result<bool, ForestDataError> {
+    let xml = read_xml_input(input)?;
+    let property = ForestPropertyData::try_from_xml_str(&xml)?;
+    let mut valid = true;
+
+    let written = property.to_xml(&XmlWriteOptions::default())?;
+    let written = String::from_utf8_lossy(&written);
+    for difference in compare_xml(&xml, &written)? {
+        println!("Round trip: {}", difference);
+        valid = false;
+    }
+
+    if let Some(tolerance) = area_tolerance {
+        for mismatch in property.check_areas(AreaTolerance::Hectares(tolerance))? {
+            println!(
+                "Area: stand {} (number {}) is {:.2} ha, polygon {:.2} ha",
+                mismatch.stand_id, mismatch.stand_number, mismatch.registered_ha, mismatch.computed_ha
+            );
+            valid = false;
+        }
+    }
+
+    if summaries {
+        for deviation in property.check_summaries(SummaryTolerance::default()) {
+            println!("Summary: {}", deviation);
+            valid = false;
         }
-        // If the JSON is null or another type, handle as needed (empty or custom behavior)
-        _ => {}
     }
 
-    "<ForestPropertyData".to_owned() + &attributes + ">"
+    if valid {
+        println!("OK");
+    }
+    Ok(valid)
 }
 
-fn read_file_without_bom(path: &str) -> io::Result<String> {
-    let mut file = File::open(path)?;
-    let mut buffer = Vec::new();
-    file.read_to_end(&mut buffer)?;
+fn xml_options(output: &OutputArgs, xml: &XmlArgs) -> XmlWriteOptions {
+    XmlWriteOptions {
+        prefix_style: match xml.prefixes {
+            Prefixes::Metsakeskus => PrefixStyle::Metsakeskus,
+            Prefixes::Bare => PrefixStyle::Bare,
+        },
+        encoding: xml.encoding.clone(),
+        indent: if output.compact { None } else { Some(2) },
+        ..XmlWriteOptions::default()
+    }
+}
+
+fn json_string<T: serde::Serialize>(value: &T, compact: bool) -> Result<String, ForestDataError> {
+    let json = if compact { serde_json::to_string(value) } else { serde_json::to_string_pretty(value) };
+    json.map_err(|e| ForestDataError::Serialization(e.to_string()))
+}
 
-    // Check if buffer starts with UTF-8 BOM and skip it if present
-    let content = if buffer.starts_with(&[0xEF, 0xBB, 0xBF]) {
-        println!("BOM detected");
-        String::from_utf8_lossy(&buffer[3..]).to_string()
+fn read_input(path: &str) -> Result<Vec<u8>, ForestDataError> {
+    if path == "-" {
+        let mut data = Vec::new();
+        std::io::stdin().read_to_end(&mut data)?;
+        Ok(data)
     } else {
-        String::from_utf8_lossy(&buffer).to_string()
-    };
+        Ok(fs::read(path)?)
+    }
+}
+
+fn read_xml_input(path: &str) -> Result<String, ForestDataError> {
+    if path == "-" {
+        decode_xml(&read_input(path)?)
+    } else {
+        read_xml_file(path)
+    }
+}
 
-    Ok(content)
+fn write_output(path: &str, data: &[u8]) -> Result<(), ForestDataError> {
+    if path == "-" {
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(data)?;
+        stdout.flush()?;
+    } else {
+        fs::write(path, data)?;
+    }
+    Ok(())
 }
 
 fn get_standard(xml_string: &str) -> Option<String> {
@@ -167,54 +524,3 @@ fn get_standard(xml_string: &str) -> Option<String> {
     re.captures(xml_string)
         .and_then(|cap| cap.get(1).map(|m| m.as_str().to_string()))
 }
-
-fn add_prefixes(xml_string: &mut String) {
-    let mut tag_prefix_map = HashMap::new();
-    tag_prefix_map.insert("RealEstates", "re");
-    tag_prefix_map.insert("RealEstate", "re");
-    tag_prefix_map.insert("Parcels", "re");
-    tag_prefix_map.insert("Parcel", "re");
-    tag_prefix_map.insert("Stands", "st");
-    tag_prefix_map.insert("Stand", "st");
-    tag_prefix_map.insert("StandBasicData", "st");
-    tag_prefix_map.insert("Identifiers", "st");
-    tag_prefix_map.insert("Identifier", "st");
-    tag_prefix_map.insert("PolygonGeometry", "gdt");
-    tag_prefix_map.insert("PointProperty", "gml");
-    tag_prefix_map.insert("Point", "gml");
-    tag_prefix_map.insert("PolygonProperty", "gml");
-    tag_prefix_map.insert("Polygon", "gml");
-    tag_prefix_map.insert("interior", "gml");
-    tag_prefix_map.insert("LinearRing ", "gml");
-    tag_prefix_map.insert("exterior", "gml");
-    tag_prefix_map.insert("SpecialFeatures", "st");
-    tag_prefix_map.insert("SpecialFeature", "st");
-    tag_prefix_map.insert("Operations", "op");
-    tag_prefix_map.insert("Operation", "op");
-    tag_prefix_map.insert("CompletionData", "op");
-    tag_prefix_map.insert("Specifications", "op");
-    tag_prefix_map.insert("Specification", "op");
-    tag_prefix_map.insert("Silviculture", "op");
-    tag_prefix_map.insert("ProposalData", "op");
-    tag_prefix_map.insert("Cutting", "op");
-    tag_prefix_map.insert("Assortments", "op");
-    tag_prefix_map.insert("Assortment", "op");
-    tag_prefix_map.insert("TreeStandData", "ts");
-    tag_prefix_map.insert("TreeStandDataDate", "ts");
-    tag_prefix_map.insert("DeadTreeStrata", "dts");
-    tag_prefix_map.insert("DeadTreeStratum", "dts");
-    tag_prefix_map.insert("TreeStrata", "tst");
-    tag_prefix_map.insert("TreeStratum", "tst");
-    tag_prefix_map.insert("TreeStandSummary", "tss");
-
-    for (tag, prefix) in &tag_prefix_map {
-        let prefixed_tag = format!("<{}:{tag}", prefix);
-        let tag_pattern = format!("<{tag}");
-        let prefixed_end_tag = format!("</{}:{tag}>", prefix);
-        let end_tag_pattern = format!("</{tag}>");
-
-        // Replace tags
-        *xml_string = xml_string.replace(&tag_pattern, &prefixed_tag);
-        *xml_string = xml_string.replace(&end_tag_pattern, &prefixed_end_tag);
-    }
-}
\ No newline at end of file
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use chrono::NaiveDateTime;
+use crate::error::ForestDataError;
+use crate::forest_property_data::{ForestPropertyData, ReParcel, ReRealEstate, StStand};
+use crate::values::with_canonical_text;
+
+// What to do when the same stand id has different content in two documents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictPolicy {
+    // Keep the stand with the latest ChangeTime; the earlier document wins ties
+    #[default]
+    NewestChangeTime,
+    FirstWins,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict {
+    pub stand_id: String,
+    // Whether the stand from the later document replaced the earlier one
+    pub replaced: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeReport {
+    pub added_stands: usize,
+    // Stands that were identical in both documents
+    pub duplicate_stands: usize,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl ForestPropertyData {
+    // Merges `others` into this document by RealEstate, Parcel and Stand id.
+    // Root attributes, header comments and real estate and parcel data are
+    // kept from the first document that has them. With `ConflictPolicy::Error`
+    // the merge stops at the first conflict and this document is left unchanged.
+    pub fn merge<I>(&mut self, others: I, policy: ConflictPolicy) -> Result<MergeReport, ForestDataError>
+    where
+        I: IntoIterator<Item = ForestPropertyData>,
+    {
+        let mut merged = self.clone();
+        let report = merged.merge_into(others, policy)?;
+        *self = merged;
+        Ok(report)
+    }
+
+    fn merge_into<I>(&mut self, others: I, policy: ConflictPolicy) -> Result<MergeReport, ForestDataError>
+    where
+        I: IntoIterator<Item = ForestPropertyData>,
+    {
+        let mut report = MergeReport::default();
+
+        for other in others {
+            if let Some(incoming) = other.st_stands {
+                match self.st_stands.as_mut() {
+                    Some(stands) => merge_stands(&mut stands.st_stand, incoming.st_stand, policy, &mut report)?,
+                    None => {
+                        report.added_stands += incoming.st_stand.len();
+                        self.st_stands = Some(incoming);
+                    }
+                }
+            }
+
+            if let Some(incoming) = other.re_real_estates {
+                match self.re_real_estates.as_mut() {
+                    Some(estates) => merge_estates(&mut estates.re_real_estate, incoming.re_real_estate, policy, &mut report)?,
+                    None => {
+                        report.added_stands += incoming.re_real_estate.iter().map(estate_stand_count).sum::<usize>();
+                        self.re_real_estates = Some(incoming);
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+fn merge_estates(
+    existing: &mut Vec<ReRealEstate>,
+    incoming: Vec<ReRealEstate>,
+    policy: ConflictPolicy,
+    report: &mut MergeReport,
+) -> Result<(), ForestDataError> {
+    for estate in incoming {
+        match existing.iter_mut().find(|e| e.id == estate.id) {
+            Some(current) => merge_parcels(&mut current.re_parcels.re_parcel, estate.re_parcels.re_parcel, policy, report)?,
+            None => {
+                report.added_stands += estate_stand_count(&estate);
+                existing.push(estate);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn merge_parcels(
+    existing: &mut Vec<ReParcel>,
+    incoming: Vec<ReParcel>,
+    policy: ConflictPolicy,
+    report: &mut MergeReport,
+) -> Result<(), ForestDataError> {
+    for parcel in incoming {
+        match existing.iter_mut().find(|p| p.id == parcel.id) {
+            Some(current) => merge_stands(&mut current.st_stands.st_stand, parcel.st_stands.st_stand, policy, report)?,
+            None => {
+                report.added_stands += parcel.st_stands.st_stand.len();
+                existing.push(parcel);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn merge_stands(
+    existing: &mut Vec<StStand>,
+    incoming: Vec<StStand>,
+    policy: ConflictPolicy,
+    report: &mut MergeReport,
+) -> Result<(), ForestDataError> {
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for (position, stand) in existing.iter().enumerate() {
+        positions.entry(stand.id.clone()).or_insert(position);
+    }
+
+    for stand in incoming {
+        let Some(&position) = positions.get(&stand.id) else {
+            report.added_stands += 1;
+            positions.insert(stand.id.clone(), existing.len());
+            existing.push(stand);
+            continue;
+        };
+        let current = &mut existing[position];
+
+        if same_content(current, &stand)? {
+            report.duplicate_stands += 1;
+            continue;
+        }
+
+        let replaced = match policy {
+            ConflictPolicy::NewestChangeTime => latest_change_time(&stand) > latest_change_time(current),
+            ConflictPolicy::FirstWins => false,
+            ConflictPolicy::Error => {
+                return Err(ForestDataError::Merge(format!("Stand {} has different content in the merged documents", stand.id)));
+            }
+        };
+
+        report.conflicts.push(MergeConflict { stand_id: stand.id.clone(), replaced });
+        if replaced {
+            *current = stand;
+        }
+    }
+    Ok(())
+}
+
+// Compares typed values, not their text
+fn same_content(a: &StStand, b: &StStand) -> Result<bool, ForestDataError> {
+    with_canonical_text(|| {
+        let a = serde_json::to_value(a).map_err(|e| ForestDataError::Serialization(e.to_string()))?;
+        let b = serde_json::to_value(b).map_err(|e| ForestDataError::Serialization(e.to_string()))?;
+        Ok(a == b)
+    })
+}
+
+// The latest ChangeTime in the stand: basic data, tree strata, summaries,
+// operations, assortments and special features
+pub fn latest_change_time(stand: &StStand) -> Option<NaiveDateTime> {
+    let dates = stand.ts_tree_stand_data.iter().flat_map(|data| &data.ts_tree_stand_data_date);
+    let strata = dates.clone()
+        .flat_map(|date| date.tst_tree_strata.iter().flat_map(|strata| &strata.tst_tree_stratum))
+        .filter_map(|stratum| stratum.co_change_time.as_ref());
+    let dead_strata = dates.clone()
+        .flat_map(|date| date.dts_dead_tree_strata.iter().flat_map(|strata| &strata.dts_dead_tree_stratum))
+        .filter_map(|stratum| stratum.co_change_time.as_ref());
+    let summaries = dates
+        .filter_map(|date| date.tss_tree_stand_summary.as_ref())
+        .filter_map(|summary| summary.co_change_time.as_ref());
+
+    let operations = stand.op_operations.iter().flat_map(|operations| &operations.op_operation);
+    let assortments = operations.clone()
+        .filter_map(|operation| operation.op_cutting.as_ref())
+        .flat_map(|cutting| cutting.op_assortments.iter().flat_map(|assortments| &assortments.op_assortment))
+        .filter_map(|assortment| assortment.co_change_time.as_ref());
+    let operations = operations.filter_map(|operation| operation.co_change_time.as_ref());
+
+    let features = stand.st_special_features.iter()
+        .flat_map(|features| &features.st_special_feature)
+        .filter_map(|feature| feature.co_change_time.as_ref());
+
+    stand.st_stand_basic_data.co_change_time.iter()
+        .chain(strata)
+        .chain(dead_strata)
+        .chain(summaries)
+        .chain(operations)
+        .chain(assortments)
+        .chain(features)
+        .map(|time| time.get())
+        .max()
+}
+
+fn estate_stand_count(estate: &ReRealEstate) -> usize {
+    estate.re_parcels.re_parcel.iter().map(|parcel| parcel.st_stands.st_stand.len()).sum()
+}
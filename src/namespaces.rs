@@ -0,0 +1,376 @@
+use std::collections::HashMap;
+use quick_xml::events::{BytesEnd, BytesStart, Event};
+use quick_xml::name::{PrefixDeclaration, ResolveResult};
+use quick_xml::{NsReader, Reader, Writer};
+use crate::error::{ForestDataError, XmlError};
+
+const FOREST_DATA_URI: &str = "http://standardit.tapio.fi/schemas/forestData";
+
+// The XML namespaces used by the Metsäkeskus forest data schemas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Namespace {
+    ForestData,
+    RealEstate,
+    Stand,
+    TreeStand,
+    TreeStratum,
+    DeadTreeStrata,
+    TreeStandSummary,
+    Operation,
+    SpecialFeature,
+    GeometricDataTypes,
+    Common,
+    Gml,
+    Xsi,
+    Xlink,
+}
+
+impl Namespace {
+    pub const ALL: [Namespace; 14] = [
+        Namespace::ForestData,
+        Namespace::RealEstate,
+        Namespace::Stand,
+        Namespace::TreeStand,
+        Namespace::TreeStratum,
+        Namespace::DeadTreeStrata,
+        Namespace::TreeStandSummary,
+        Namespace::Operation,
+        Namespace::SpecialFeature,
+        Namespace::GeometricDataTypes,
+        Namespace::Common,
+        Namespace::Gml,
+        Namespace::Xsi,
+        Namespace::Xlink,
+    ];
+
+    // The namespace URI of the current schema version
+    pub fn uri(&self) -> &'static str {
+        match self {
+            Namespace::ForestData => FOREST_DATA_URI,
+            Namespace::RealEstate => "http://standardit.tapio.fi/schemas/forestData/realEstate",
+            Namespace::Stand => "http://standardit.tapio.fi/schemas/forestData/Stand",
+            Namespace::TreeStand => "http://standardit.tapio.fi/schemas/forestData/treeStand",
+            Namespace::TreeStratum => "http://standardit.tapio.fi/schemas/forestData/treeStratum",
+            Namespace::DeadTreeStrata => "http://standardit.tapio.fi/schemas/forestData/deadTreeStrata",
+            Namespace::TreeStandSummary => "http://standardit.tapio.fi/schemas/forestData/treeStandSummary",
+            Namespace::Operation => "http://standardit.tapio.fi/schemas/forestData/operation",
+            Namespace::SpecialFeature => "http://standardit.tapio.fi/schemas/forestData/specialFeature",
+            Namespace::GeometricDataTypes => "http://standardit.tapio.fi/schemas/forestData/common/geometricDataTypes",
+            Namespace::Common => "http://standardit.tapio.fi/schemas/forestData/common",
+            Namespace::Gml => "http://www.opengis.net/gml",
+            Namespace::Xsi => "http://www.w3.org/2001/XMLSchema-instance",
+            Namespace::Xlink => "http://www.w3.org/1999/xlink",
+        }
+    }
+
+    // The prefix used in Metsäkeskus exports. The root namespace is the default namespace.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Namespace::ForestData => "",
+            Namespace::RealEstate => "re",
+            Namespace::Stand => "st",
+            Namespace::TreeStand => "ts",
+            Namespace::TreeStratum => "tst",
+            Namespace::DeadTreeStrata => "dts",
+            Namespace::TreeStandSummary => "tss",
+            Namespace::Operation => "op",
+            Namespace::SpecialFeature => "sf",
+            Namespace::GeometricDataTypes => "gdt",
+            Namespace::Common => "co",
+            Namespace::Gml => "gml",
+            Namespace::Xsi => "xsi",
+            Namespace::Xlink => "xlink",
+        }
+    }
+
+    // Recognizes a namespace URI of any schema version, e.g. both
+    // `.../forestData/Stand` and `.../forestData/stand/2010/08/31`
+    pub fn from_uri(uri: &str) -> Option<Namespace> {
+        let uri = uri.trim();
+        if uri.starts_with("http://www.opengis.net/gml") {
+            return Some(Namespace::Gml);
+        }
+        if uri == Namespace::Xsi.uri() {
+            return Some(Namespace::Xsi);
+        }
+        if uri == Namespace::Xlink.uri() {
+            return Some(Namespace::Xlink);
+        }
+
+        let rest = uri.strip_prefix(FOREST_DATA_URI)?.to_lowercase();
+        let rest = strip_version(rest.trim_end_matches('/'));
+
+        match rest {
+            "" => Some(Namespace::ForestData),
+            "/realestate" => Some(Namespace::RealEstate),
+            "/stand" => Some(Namespace::Stand),
+            "/treestand" => Some(Namespace::TreeStand),
+            "/treestratum" => Some(Namespace::TreeStratum),
+            "/deadtreestrata" => Some(Namespace::DeadTreeStrata),
+            "/treestandsummary" => Some(Namespace::TreeStandSummary),
+            "/operation" => Some(Namespace::Operation),
+            "/specialfeature" => Some(Namespace::SpecialFeature),
+            "/common/geometricdatatypes" => Some(Namespace::GeometricDataTypes),
+            "/common" => Some(Namespace::Common),
+            _ => None,
+        }
+    }
+}
+
+// Strips a trailing `/yyyy/mm/dd` schema version from a namespace path
+fn strip_version(path: &str) -> &str {
+    let parts: Vec<&str> = path.rsplitn(4, '/').collect();
+    let is_date = parts.len() == 4
+        && parts[..3].iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+        && parts[2].len() == 4;
+
+    if is_date {
+        parts[3]
+    } else {
+        path
+    }
+}
+
+// Returns the namespace of an element from its local name and its parent element
+pub fn element_namespace(parent: Option<(Namespace, &str)>, local_name: &str) -> Namespace {
+    match local_name {
+        "ForestPropertyData" => Namespace::ForestData,
+        "ChangeState" | "ChangeTime" | "DataSource" | "IdentifierType" | "IdentifierValue" => Namespace::Common,
+        "PolygonGeometry" => Namespace::GeometricDataTypes,
+        "pointProperty" | "Point" | "polygonProperty" | "Polygon" | "exterior" | "interior" | "LinearRing" | "coordinates" => Namespace::Gml,
+        "RealEstates" | "RealEstate" | "Parcels" | "Parcel" => Namespace::RealEstate,
+        "Stands" | "Stand" | "StandBasicData" | "Identifiers" | "Identifier" | "SpecialFeatures" | "SpecialFeature" => Namespace::Stand,
+        "Operations" | "Operation" => Namespace::Operation,
+        "TreeStandData" | "TreeStandDataDate" => Namespace::TreeStand,
+        "TreeStrata" | "TreeStratum" => Namespace::TreeStratum,
+        "DeadTreeStrata" | "DeadTreeStratum" => Namespace::DeadTreeStrata,
+        "TreeStandSummary" => Namespace::TreeStandSummary,
+        _ => match parent {
+            Some((_, "SpecialFeature")) => Namespace::SpecialFeature,
+            Some((namespace, _)) => namespace,
+            None => Namespace::ForestData,
+        },
+    }
+}
+
+// How element names are prefixed when writing XML
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum PrefixStyle {
+    // Unprefixed names in the default namespace, as written by earlier versions of this crate
+    Bare,
+    // The prefixes used in Metsäkeskus exports: `re:`, `st:`, `op:`, ...
+    #[default]
+    Metsakeskus,
+    // Caller-chosen prefixes. Namespaces missing from the map use the Metsäkeskus prefix.
+    Custom(HashMap<Namespace, String>),
+}
+
+impl PrefixStyle {
+    fn prefix(&self, namespace: Namespace) -> &str {
+        match self {
+            PrefixStyle::Bare => "",
+            PrefixStyle::Metsakeskus => namespace.prefix(),
+            PrefixStyle::Custom(map) => map.get(&namespace).map_or(namespace.prefix(), |p| p.as_str()),
+        }
+    }
+
+    // The prefix of a namespace declaration on the root element
+    fn declaration_prefix(&self, namespace: Namespace) -> &str {
+        match self {
+            PrefixStyle::Bare => namespace.prefix(),
+            _ => self.prefix(namespace),
+        }
+    }
+}
+
+// The attribute name that declares a namespace with `prefix`
+fn declaration(prefix: &str) -> String {
+    if prefix.is_empty() {
+        "xmlns".to_string()
+    } else {
+        format!("xmlns:{}", prefix)
+    }
+}
+
+fn qualified(prefix: &str, local_name: &str) -> String {
+    if prefix.is_empty() {
+        local_name.to_string()
+    } else {
+        format!("{}:{}", prefix, local_name)
+    }
+}
+
+// Resolves the element namespaces of a document and rewrites it with
+// unprefixed element names and the Metsäkeskus prefixes on the root namespace
+// declarations, which is the form the model deserializes. Every element must
+// be bound to the namespace URI the schema defines for it, with any prefix.
+// Elements in no namespace, in an unknown namespace or in another known
+// namespace are rejected.
+//
+// Documents written with `PrefixStyle::Bare` have every element in the
+// default ForestData namespace. A document is read as one if its first
+// element outside the ForestData namespace is in the default namespace, and
+// then all its elements must be.
+pub(crate) fn normalize(xml: &str) -> Result<String, ForestDataError> {
+    let mut reader = NsReader::from_str(xml);
+    let mut writer = Writer::new(Vec::with_capacity(xml.len()));
+    let mut stack: Vec<(Namespace, String)> = Vec::new();
+    let mut bare: Option<bool> = None;
+
+    loop {
+        let position = reader.buffer_position() as usize;
+        let event = reader.read_event().map_err(|e| syntax_error(xml, reader.error_position() as usize, &stack, e))?;
+
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let local = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                let parent = stack.last().map(|(ns, name)| (*ns, name.as_str()));
+                let expected = element_namespace(parent, &local);
+
+                let (resolved, _) = reader.resolve_element(e.name());
+                let (actual, uri) = match resolved {
+                    ResolveResult::Bound(uri) => {
+                        let uri = String::from_utf8_lossy(uri.as_ref()).to_string();
+                        match Namespace::from_uri(&uri) {
+                            Some(actual) => (actual, uri),
+                            None => {
+                                let message = format!("element `{}` is in unknown namespace `{}`", local, uri);
+                                return Err(located_error(xml, position, &stack, &local, message));
+                            }
+                        }
+                    }
+                    ResolveResult::Unbound => {
+                        let message = format!("element `{}` is not in a namespace, expected `{}`", local, expected.uri());
+                        return Err(located_error(xml, position, &stack, &local, message));
+                    }
+                    ResolveResult::Unknown(prefix) => {
+                        let message = format!("element `{}` has undeclared prefix `{}`", local, String::from_utf8_lossy(&prefix));
+                        return Err(located_error(xml, position, &stack, &local, message));
+                    }
+                };
+
+                if expected != Namespace::ForestData && bare.is_none() {
+                    bare = Some(actual == Namespace::ForestData);
+                }
+                if actual != expected && !(bare == Some(true) && actual == Namespace::ForestData) {
+                    let message = format!("element `{}` is in namespace `{}`, expected `{}`", local, uri, expected.uri());
+                    return Err(located_error(xml, position, &stack, &local, message));
+                }
+
+                let mut start = BytesStart::new(local.clone());
+                let mut names: Vec<String> = Vec::new();
+                for attr in e.attributes() {
+                    let attr = attr.map_err(|e| syntax_error(xml, position, &stack, e))?;
+                    let value = attr.unescape_value().map_err(|e| syntax_error(xml, position, &stack, e))?;
+
+                    let name = match attr.key.as_namespace_binding() {
+                        Some(PrefixDeclaration::Default) => "xmlns".to_string(),
+                        Some(PrefixDeclaration::Named(prefix)) => match Namespace::from_uri(&value) {
+                            Some(namespace) => declaration(namespace.prefix()),
+                            None => declaration(&String::from_utf8_lossy(prefix)),
+                        },
+                        None => String::from_utf8_lossy(attr.key.local_name().as_ref()).to_string(),
+                    };
+
+                    // A namespace may be declared both as default and with a prefix
+                    if !names.contains(&name) {
+                        start.push_attribute((name.as_str(), value.as_ref()));
+                        names.push(name);
+                    }
+                }
+
+                if matches!(event, Event::Start(_)) {
+                    writer.write_event(Event::Start(start)).map_err(ForestDataError::Io)?;
+                    stack.push((expected, local));
+                } else {
+                    writer.write_event(Event::Empty(start)).map_err(ForestDataError::Io)?;
+                }
+            }
+            Event::End(_) => {
+                let local = stack.pop().map(|(_, local)| local).unwrap_or_default();
+                writer.write_event(Event::End(BytesEnd::new(local))).map_err(ForestDataError::Io)?;
+            }
+            Event::Eof => break,
+            other => writer.write_event(other).map_err(ForestDataError::Io)?,
+        }
+    }
+
+    String::from_utf8(writer.into_inner()).map_err(|e| ForestDataError::Encoding(e.to_string()))
+}
+
+// Rewrites XML produced by the serializer with element prefixes in the given style,
+// optionally indenting nested elements. The root element gets its namespace
+// declarations renamed to match and `schemaLocation` is written in the XML
+// Schema instance namespace.
+pub(crate) fn apply_prefixes(xml: &str, style: &PrefixStyle, indent: Option<usize>) -> Result<String, ForestDataError> {
+    let mut reader = Reader::from_str(xml);
+    let buffer = Vec::with_capacity(xml.len() * 2);
+    let mut writer = match indent {
+        Some(size) => Writer::new_with_indent(buffer, b' ', size),
+        None => Writer::new(buffer),
+    };
+    let mut stack: Vec<(Namespace, String, String)> = Vec::new();
+
+    loop {
+        let event = reader.read_event().map_err(|e| syntax_error(xml, reader.error_position() as usize, &[], e))?;
+
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let local = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                let parent = stack.last().map(|(ns, name, _)| (*ns, name.as_str()));
+                let namespace = element_namespace(parent, &local);
+                let name = qualified(style.prefix(namespace), &local);
+                let is_root = stack.is_empty();
+
+                let mut start = BytesStart::new(name.clone());
+                for attr in e.attributes() {
+                    let attr = attr.map_err(|e| syntax_error(xml, 0, &[], e))?;
+                    let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
+                    let value = attr.unescape_value().map_err(|e| syntax_error(xml, 0, &[], e))?;
+
+                    let key = match key.strip_prefix("xmlns:") {
+                        Some(prefix) if is_root => {
+                            let namespace = Namespace::ALL.iter().find(|ns| ns.prefix() == prefix);
+                            match namespace {
+                                Some(&ns) => declaration(style.declaration_prefix(ns)),
+                                None => key.clone(),
+                            }
+                        }
+                        _ if is_root && key == "xmlns" => declaration(style.prefix(Namespace::ForestData)),
+                        _ if is_root && key == "schemaLocation" => qualified(style.declaration_prefix(Namespace::Xsi), "schemaLocation"),
+                        _ => key.clone(),
+                    };
+                    start.push_attribute((key.as_str(), value.as_ref()));
+                }
+
+                if matches!(event, Event::Start(_)) {
+                    writer.write_event(Event::Start(start)).map_err(ForestDataError::Io)?;
+                    stack.push((namespace, local, name));
+                } else {
+                    writer.write_event(Event::Empty(start)).map_err(ForestDataError::Io)?;
+                }
+            }
+            Event::End(_) => {
+                let name = stack.pop().map(|(_, _, name)| name).unwrap_or_default();
+                writer.write_event(Event::End(BytesEnd::new(name))).map_err(ForestDataError::Io)?;
+            }
+            Event::Eof => break,
+            other => writer.write_event(other).map_err(ForestDataError::Io)?,
+        }
+    }
+
+    String::from_utf8(writer.into_inner()).map_err(|e| ForestDataError::Encoding(e.to_string()))
+}
+
+fn element_path(stack: &[(Namespace, String)], last: Option<&str>) -> String {
+    stack.iter().skip(1).map(|(_, name)| name.as_str()).chain(last).collect::<Vec<_>>().join("/")
+}
+
+fn located_error(xml: &str, position: usize, stack: &[(Namespace, String)], local: &str, message: String) -> ForestDataError {
+    let path = if stack.is_empty() { String::new() } else { element_path(stack, Some(local)) };
+    XmlError::at(xml, position, path, message).into()
+}
+
+fn syntax_error(xml: &str, position: usize, stack: &[(Namespace, String)], e: impl std::fmt::Display) -> ForestDataError {
+    XmlError::at(xml, position, element_path(stack, None), e.to_string()).into()
+}
@@ -0,0 +1,217 @@
+use geo::MapCoords;
+use geo_types::{Coord, Polygon};
+use crate::error::ForestDataError;
+use crate::forest_property_data::{ForestPropertyData, StStand};
+use crate::geometry::{format_coordinates, parse_coordinates};
+
+// GRS80 ellipsoid and the ETRS-TM35FIN projection parameters (JHS 154)
+const A: f64 = 6_378_137.0;
+const F: f64 = 1.0 / 298.257_222_101;
+const K0: f64 = 0.9996;
+const LON0_DEGREES: f64 = 27.0;
+const FALSE_EASTING: f64 = 500_000.0;
+
+// Coordinate reference systems used in MV files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Crs {
+    // ETRS89 / TM35FIN, easting and northing in metres
+    Tm35Fin,
+    // WGS84 longitude and latitude in degrees. ETRS89 and WGS84 differ by less
+    // than a metre in Finland, which is below the accuracy of stand boundaries.
+    Wgs84,
+}
+
+impl Crs {
+    // Recognizes the srsName forms seen in MV and GML files
+    pub fn from_srs_name(srs_name: &str) -> Option<Crs> {
+        let name = srs_name.trim().to_ascii_uppercase();
+        let code = name.rsplit([':', '#', '/']).next().unwrap_or("");
+
+        match name.as_str() {
+            "EUREF-FIN" | "EUREF_FIN" | "ETRS-TM35FIN" | "ETRS89 / TM35FIN" | "TM35FIN" => Some(Crs::Tm35Fin),
+            "WGS84" | "WGS 84" | "CRS84" => Some(Crs::Wgs84),
+            _ if name.contains("EPSG") && code == "3067" => Some(Crs::Tm35Fin),
+            _ if name.contains("EPSG") && code == "4326" => Some(Crs::Wgs84),
+            _ if name.contains("CRS84") => Some(Crs::Wgs84),
+            _ => None,
+        }
+    }
+
+    // EPSG:4326 has latitude first, so WGS84 coordinates are written as
+    // CRS84, which has the longitude,latitude order used here
+    pub fn srs_name(&self) -> &'static str {
+        match self {
+            Crs::Tm35Fin => "EPSG:3067",
+            Crs::Wgs84 => "urn:ogc:def:crs:OGC:1.3:CRS84",
+        }
+    }
+
+    // Decimals written to gml:coordinates, about a millimetre in both systems
+    pub(crate) fn decimals(&self) -> usize {
+        match self {
+            Crs::Tm35Fin => 4,
+            Crs::Wgs84 => 9,
+        }
+    }
+}
+
+// Coefficients of the Krüger series, in powers of the third flattening n
+struct Series {
+    n: f64,
+    e: f64,
+    a1: f64,
+}
+
+impl Series {
+    fn grs80() -> Series {
+        let n = F / (2.0 - F);
+        let e = (F * (2.0 - F)).sqrt();
+        let a1 = A / (1.0 + n) * (1.0 + n.powi(2) / 4.0 + n.powi(4) / 64.0);
+        Series { n, e, a1 }
+    }
+
+    fn forward(&self) -> [f64; 4] {
+        let n = self.n;
+        [
+            n / 2.0 - 2.0 / 3.0 * n.powi(2) + 5.0 / 16.0 * n.powi(3) + 41.0 / 180.0 * n.powi(4),
+            13.0 / 48.0 * n.powi(2) - 3.0 / 5.0 * n.powi(3) + 557.0 / 1440.0 * n.powi(4),
+            61.0 / 240.0 * n.powi(3) - 103.0 / 140.0 * n.powi(4),
+            49561.0 / 161280.0 * n.powi(4),
+        ]
+    }
+
+    fn inverse(&self) -> [f64; 4] {
+        let n = self.n;
+        [
+            n / 2.0 - 2.0 / 3.0 * n.powi(2) + 37.0 / 96.0 * n.powi(3) - 1.0 / 360.0 * n.powi(4),
+            1.0 / 48.0 * n.powi(2) + 1.0 / 15.0 * n.powi(3) - 437.0 / 1440.0 * n.powi(4),
+            17.0 / 480.0 * n.powi(3) - 37.0 / 840.0 * n.powi(4),
+            4397.0 / 161280.0 * n.powi(4),
+        ]
+    }
+}
+
+// Converts TM35FIN easting/northing to WGS84 longitude/latitude
+pub fn tm35fin_to_wgs84(coord: Coord<f64>) -> Coord<f64> {
+    let series = Series::grs80();
+    let xi = coord.y / (series.a1 * K0);
+    let eta = (coord.x - FALSE_EASTING) / (series.a1 * K0);
+
+    let (mut xi1, mut eta1) = (xi, eta);
+    for (j, h) in series.inverse().iter().enumerate() {
+        let k = 2.0 * (j + 1) as f64;
+        xi1 -= h * (k * xi).sin() * (k * eta).cosh();
+        eta1 -= h * (k * xi).cos() * (k * eta).sinh();
+    }
+
+    let beta = (xi1.sin() / eta1.cosh()).asin();
+    let l = (eta1.tanh() / beta.cos()).asin();
+
+    let q = beta.tan().asinh();
+    let mut q1 = q + series.e * (series.e * q.tanh()).atanh();
+    for _ in 0..5 {
+        q1 = q + series.e * (series.e * q1.tanh()).atanh();
+    }
+
+    Coord { x: LON0_DEGREES + l.to_degrees(), y: q1.sinh().atan().to_degrees() }
+}
+
+// Converts WGS84 longitude/latitude to TM35FIN easting/northing
+pub fn wgs84_to_tm35fin(coord: Coord<f64>) -> Coord<f64> {
+    let series = Series::grs80();
+    let phi = coord.y.to_radians();
+    let l = (coord.x - LON0_DEGREES).to_radians();
+
+    let q = phi.tan().asinh() - series.e * (series.e * phi.sin()).atanh();
+    let beta = q.sinh().atan();
+    let eta1 = (beta.cos() * l.sin()).atanh();
+    let xi1 = (beta.sin() * eta1.cosh()).asin();
+
+    let (mut xi, mut eta) = (xi1, eta1);
+    for (j, h) in series.forward().iter().enumerate() {
+        let k = 2.0 * (j + 1) as f64;
+        xi += h * (k * xi1).sin() * (k * eta1).cosh();
+        eta += h * (k * xi1).cos() * (k * eta1).sinh();
+    }
+
+    Coord { x: series.a1 * eta * K0 + FALSE_EASTING, y: series.a1 * xi * K0 }
+}
+
+pub fn transform_coord(coord: Coord<f64>, from: Crs, to: Crs) -> Coord<f64> {
+    match (from, to) {
+        (Crs::Tm35Fin, Crs::Wgs84) => tm35fin_to_wgs84(coord),
+        (Crs::Wgs84, Crs::Tm35Fin) => wgs84_to_tm35fin(coord),
+        _ => coord,
+    }
+}
+
+// Reprojects any geo-types geometry
+pub fn reproject<G>(geometry: &G, from: Crs, to: Crs) -> G
+where
+    G: MapCoords<f64, f64, Output = G>,
+{
+    geometry.map_coords(|coord| transform_coord(coord, from, to))
+}
+
+fn parse_crs(srs_name: &str, stand_id: &str) -> Result<Crs, ForestDataError> {
+    Crs::from_srs_name(srs_name)
+        .ok_or_else(|| ForestDataError::Geometry(format!("Stand {}: unknown srsName `{}`", stand_id, srs_name)))
+}
+
+fn reproject_text(text: &str, from: Crs, to: Crs) -> Result<String, ForestDataError> {
+    if from == to {
+        return Ok(text.to_string());
+    }
+    let coords: Vec<Coord<f64>> = parse_coordinates(text)?
+        .into_iter()
+        .map(|coord| transform_coord(coord, from, to))
+        .collect();
+    Ok(format_coordinates(&coords, to.decimals()))
+}
+
+impl StStand {
+    // The coordinate reference system of the stand polygon
+    pub fn crs(&self) -> Result<Crs, ForestDataError> {
+        let polygon = &self.st_stand_basic_data.gdt_polygon_geometry.gml_polygon_property.gml_polygon;
+        parse_crs(&polygon.srs_name, &self.id)
+    }
+
+    // The stand polygon in the given coordinate reference system
+    pub fn polygon_in(&self, crs: Crs) -> Result<Polygon<f64>, ForestDataError> {
+        Ok(reproject(&self.polygon()?, self.crs()?, crs))
+    }
+
+    // Rewrites the point and polygon coordinates and srsNames of the stand
+    pub fn reproject(&mut self, crs: Crs) -> Result<(), ForestDataError> {
+        let geometry = &mut self.st_stand_basic_data.gdt_polygon_geometry;
+
+        let point = &mut geometry.gml_point_property.gml_point;
+        let from = parse_crs(&point.srs_name, &self.id)?;
+        point.gml_coordinates = reproject_text(&point.gml_coordinates, from, crs)?;
+        point.srs_name = crs.srs_name().to_string();
+
+        let polygon = &mut geometry.gml_polygon_property.gml_polygon;
+        let from = parse_crs(&polygon.srs_name, &self.id)?;
+        if let Some(exterior) = polygon.gml_exterior.as_mut() {
+            let ring = &mut exterior.gml_linear_ring;
+            ring.gml_coordinates = reproject_text(&ring.gml_coordinates, from, crs)?;
+        }
+        for interior in polygon.gml_interior.iter_mut().flatten() {
+            let ring = &mut interior.gml_linear_ring;
+            ring.gml_coordinates = reproject_text(&ring.gml_coordinates, from, crs)?;
+        }
+        polygon.srs_name = crs.srs_name().to_string();
+
+        Ok(())
+    }
+}
+
+impl ForestPropertyData {
+    // Reprojects the geometry of every stand in the document
+    pub fn reproject(&mut self, crs: Crs) -> Result<(), ForestDataError> {
+        for stand in self.stands_mut() {
+            stand.reproject(crs)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,194 @@
+use geo::{Area, BooleanOps, Contains, InteriorPoint, Relate};
+use geo_types::{Geometry, MultiPolygon, Point, Polygon};
+use wkt::TryFromWkt;
+use crate::error::ForestDataError;
+use crate::forest_property_data::{ForestPropertyData, StStand};
+use crate::geometry::{format_coordinates, parse_coordinates};
+use crate::projection::{reproject, Crs};
+use crate::values::Typed;
+
+// How a stand polygon must relate to the query boundary to be selected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpatialPredicate {
+    #[default]
+    Intersects,
+    Within,
+}
+
+// Selects stands by location, like the ByPolygon query of the Metsäkeskus REST API
+#[derive(Debug, Clone)]
+pub struct SpatialQuery {
+    pub boundary: Geometry<f64>,
+    // Coordinate reference system of `boundary`
+    pub crs: Crs,
+    pub predicate: SpatialPredicate,
+    // Cut the selected stand polygons to the boundary
+    pub clip: bool,
+}
+
+impl SpatialQuery {
+    pub fn new(boundary: Geometry<f64>) -> SpatialQuery {
+        SpatialQuery { boundary, crs: Crs::Tm35Fin, predicate: SpatialPredicate::default(), clip: false }
+    }
+
+    // Parses a WKT geometry, e.g. the `wktPolygon` parameter of a ByPolygon URL
+    pub fn from_wkt(wkt: &str) -> Result<SpatialQuery, ForestDataError> {
+        let boundary = Geometry::try_from_wkt_str(wkt)
+            .map_err(|e| ForestDataError::Geometry(format!("Invalid WKT: {}", e)))?;
+        Ok(SpatialQuery::new(boundary))
+    }
+
+    pub fn matches(&self, stand: &StStand) -> Result<bool, ForestDataError> {
+        let polygon = stand.polygon_in(self.crs)?;
+        let relation = polygon.relate(&self.boundary);
+
+        Ok(match self.predicate {
+            SpatialPredicate::Intersects => relation.is_intersects(),
+            SpatialPredicate::Within => relation.is_within(),
+        })
+    }
+
+    fn clip_boundary(&self) -> Result<MultiPolygon<f64>, ForestDataError> {
+        match &self.boundary {
+            Geometry::Polygon(polygon) => Ok(MultiPolygon::new(vec![polygon.clone()])),
+            Geometry::MultiPolygon(polygons) => Ok(polygons.clone()),
+            Geometry::Rect(rect) => Ok(MultiPolygon::new(vec![rect.to_polygon()])),
+            Geometry::Triangle(triangle) => Ok(MultiPolygon::new(vec![triangle.to_polygon()])),
+            _ => Err(ForestDataError::Geometry("Only polygonal boundaries can be used for clipping".to_string())),
+        }
+    }
+}
+
+impl StStand {
+    // Replaces the stand polygon with its intersection with `boundary`, given in `crs`.
+    // A stand has a single polygon, so if the boundary cuts the stand into
+    // several parts only the largest one is kept. Area and AreaDecrease are
+    // scaled by the share of the polygon that remains.
+    // Returns false if nothing of the stand is inside the boundary.
+    pub fn clip_to(&mut self, boundary: &MultiPolygon<f64>, crs: Crs) -> Result<bool, ForestDataError> {
+        let stand_crs = self.crs()?;
+        let polygon = self.polygon_in(crs)?;
+        let clipped = polygon.intersection(boundary);
+
+        let Some(part) = clipped.0.into_iter().max_by(|a, b| a.unsigned_area().total_cmp(&b.unsigned_area())) else {
+            return Ok(false);
+        };
+        let ratio = part.unsigned_area() / polygon.unsigned_area();
+        if ratio == 0.0 {
+            return Ok(false);
+        }
+
+        self.set_polygon(&reproject(&part, crs, stand_crs), stand_crs)?;
+
+        let basic_data = &mut self.st_stand_basic_data;
+        basic_data.st_area = scaled_area(&basic_data.st_area, ratio)?;
+        if let Some(decrease) = basic_data.st_area_decrease.as_mut() {
+            *decrease = scaled_area(decrease, ratio)?;
+        }
+
+        Ok(true)
+    }
+
+    fn set_polygon(&mut self, polygon: &Polygon<f64>, crs: Crs) -> Result<(), ForestDataError> {
+        let decimals = crs.decimals();
+        let geometry = &mut self.st_stand_basic_data.gdt_polygon_geometry;
+        let gml_polygon = &mut geometry.gml_polygon_property.gml_polygon;
+
+        let exterior = gml_polygon.gml_exterior.as_mut()
+            .ok_or_else(|| ForestDataError::Geometry(format!("Stand {}: polygon has no exterior ring", self.id)))?;
+        exterior.gml_linear_ring.gml_coordinates = format_coordinates(&polygon.exterior().0, decimals);
+
+        // Interior rings that survived clipping keep their order, the rest are dropped
+        let mut interiors = polygon.interiors().iter();
+        if let Some(gml_interiors) = gml_polygon.gml_interior.as_mut() {
+            gml_interiors.retain_mut(|gml_interior| match interiors.next() {
+                Some(ring) => {
+                    gml_interior.gml_linear_ring.gml_coordinates = format_coordinates(&ring.0, decimals);
+                    true
+                }
+                None => false,
+            });
+            if gml_interiors.is_empty() {
+                gml_polygon.gml_interior = None;
+            }
+        }
+
+        // The representative point must stay inside the stand
+        let point = &mut geometry.gml_point_property.gml_point;
+        let inside = parse_coordinates(&point.gml_coordinates)?
+            .first()
+            .is_some_and(|coord| polygon.contains(&Point::from(*coord)));
+        if !inside {
+            if let Some(interior_point) = polygon.interior_point() {
+                point.gml_coordinates = format_coordinates(&[interior_point.0], decimals);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn scaled_area(area: &Typed<f64>, ratio: f64) -> Result<Typed<f64>, ForestDataError> {
+    Typed::parse(&format!("{:.2}", area.get() * ratio)).map_err(ForestDataError::Geometry)
+}
+
+impl ForestPropertyData {
+    // Keeps only the stands selected by `query`, clipping them if requested.
+    // Parcels and real estates left without stands are removed. On error the
+    // document is left unchanged.
+    pub fn filter_stands(&mut self, query: &SpatialQuery) -> Result<(), ForestDataError> {
+        let mut filtered = self.clone();
+        filtered.filter_stands_in(query)?;
+        *self = filtered;
+        Ok(())
+    }
+
+    fn filter_stands_in(&mut self, query: &SpatialQuery) -> Result<(), ForestDataError> {
+        let boundary = if query.clip { Some(query.clip_boundary()?) } else { None };
+        let keep = |stand: &mut StStand| -> Result<bool, ForestDataError> {
+            if !query.matches(stand)? {
+                return Ok(false);
+            }
+            match &boundary {
+                Some(boundary) => stand.clip_to(boundary, query.crs),
+                None => Ok(true),
+            }
+        };
+
+        if let Some(stands) = self.st_stands.as_mut() {
+            retain_stands(&mut stands.st_stand, keep)?;
+            if stands.st_stand.is_empty() {
+                self.st_stands = None;
+            }
+        }
+
+        if let Some(estates) = self.re_real_estates.as_mut() {
+            for estate in estates.re_real_estate.iter_mut() {
+                let parcels = &mut estate.re_parcels.re_parcel;
+                for parcel in parcels.iter_mut() {
+                    retain_stands(&mut parcel.st_stands.st_stand, keep)?;
+                }
+                parcels.retain(|parcel| !parcel.st_stands.st_stand.is_empty());
+            }
+            estates.re_real_estate.retain(|estate| !estate.re_parcels.re_parcel.is_empty());
+            if estates.re_real_estate.is_empty() {
+                self.re_real_estates = None;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn retain_stands<F>(stands: &mut Vec<StStand>, mut keep: F) -> Result<(), ForestDataError>
+where
+    F: FnMut(&mut StStand) -> Result<bool, ForestDataError>,
+{
+    let mut kept = Vec::with_capacity(stands.len());
+    for stand in stands.iter_mut() {
+        kept.push(keep(stand)?);
+    }
+    let mut kept = kept.into_iter();
+    stands.retain(|_| kept.next() == Some(true));
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use std::collections::HashSet;
+use crate::forest_property_data::{ForestPropertyData, ReParcel, ReRealEstate, ReRealEstates, StStand, StStands};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitLevel {
+    RealEstate,
+    Parcel,
+    Stand,
+}
+
+// One document produced by `ForestPropertyData::split`
+pub struct SplitPart {
+    // Id of the real estate, parcel or stand the part was made for. Stands
+    // directly under the root are grouped into one part with an empty id
+    // when splitting by real estate or parcel.
+    pub id: String,
+    pub data: ForestPropertyData,
+}
+
+impl SplitPart {
+    // The file name without the extension: the id, or `stands` for the
+    // stands directly under the root
+    pub fn file_stem(&self) -> &str {
+        if self.id.is_empty() { "stands" } else { &self.id }
+    }
+}
+
+// `<file stem>.xml` for each part. Ids may repeat, e.g. the same stand id in
+// two parcels, so repeated names get a `_2`, `_3`, ... suffix instead of
+// overwriting an earlier part.
+pub fn file_names(parts: &[SplitPart]) -> Vec<String> {
+    let mut used = HashSet::new();
+    parts.iter()
+        .map(|part| {
+            let stem = part.file_stem();
+            let mut name = format!("{}.xml", stem);
+            let mut n = 1;
+            while !used.insert(name.clone()) {
+                n += 1;
+                name = format!("{}_{}.xml", stem, n);
+            }
+            name
+        })
+        .collect()
+}
+
+impl ForestPropertyData {
+    // Splits the document into one complete document per real estate, parcel
+    // or stand. Every part keeps the root attributes and header comments, and
+    // parcels and stands keep the real estate and parcel they belong to.
+    pub fn split(mut self, level: SplitLevel) -> Vec<SplitPart> {
+        let estates = self.re_real_estates.take();
+        let stands = self.st_stands.take();
+        let root = self;
+        let mut parts = Vec::new();
+
+        for estate in estates.into_iter().flat_map(|estates| estates.re_real_estate) {
+            match level {
+                SplitLevel::RealEstate => parts.push(SplitPart {
+                    id: estate.id.clone(),
+                    data: with_estate(&root, estate),
+                }),
+                SplitLevel::Parcel | SplitLevel::Stand => {
+                    let (shell, parcels) = take_parcels(estate);
+                    for parcel in parcels {
+                        if level == SplitLevel::Parcel {
+                            parts.push(SplitPart { id: parcel.id.clone(), data: with_parcel(&root, &shell, parcel) });
+                            continue;
+                        }
+                        let (parcel_shell, stands) = take_stands(parcel);
+                        for stand in stands {
+                            let id = stand.id.clone();
+                            let parcel = with_stands(&parcel_shell, vec![stand]);
+                            parts.push(SplitPart { id, data: with_parcel(&root, &shell, parcel) });
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(stands) = stands {
+            if level == SplitLevel::Stand {
+                for stand in stands.st_stand {
+                    let id = stand.id.clone();
+                    parts.push(SplitPart { id, data: with_root_stands(&root, stands_of(&stands.text, vec![stand])) });
+                }
+            } else {
+                parts.push(SplitPart { id: String::new(), data: with_root_stands(&root, stands) });
+            }
+        }
+
+        parts
+    }
+}
+
+fn with_estate(root: &ForestPropertyData, estate: ReRealEstate) -> ForestPropertyData {
+    let mut data = root.clone();
+    data.re_real_estates = Some(ReRealEstates { text: None, re_real_estate: vec![estate] });
+    data
+}
+
+fn with_parcel(root: &ForestPropertyData, estate_shell: &ReRealEstate, parcel: ReParcel) -> ForestPropertyData {
+    let mut estate = estate_shell.clone();
+    estate.re_parcels.re_parcel = vec![parcel];
+    with_estate(root, estate)
+}
+
+fn with_root_stands(root: &ForestPropertyData, stands: StStands) -> ForestPropertyData {
+    let mut data = root.clone();
+    data.st_stands = Some(stands);
+    data
+}
+
+fn with_stands(parcel_shell: &ReParcel, stands: Vec<StStand>) -> ReParcel {
+    let mut parcel = parcel_shell.clone();
+    parcel.st_stands.st_stand = stands;
+    parcel
+}
+
+fn stands_of(text: &Option<String>, stands: Vec<StStand>) -> StStands {
+    StStands { text: text.clone(), st_stand: stands }
+}
+
+// Separates the parcels from the rest of the real estate data
+fn take_parcels(mut estate: ReRealEstate) -> (ReRealEstate, Vec<ReParcel>) {
+    let parcels = std::mem::take(&mut estate.re_parcels.re_parcel);
+    (estate, parcels)
+}
+
+fn take_stands(mut parcel: ReParcel) -> (ReParcel, Vec<StStand>) {
+    let stands = std::mem::take(&mut parcel.st_stands.st_stand);
+    (parcel, stands)
+}
@@ -0,0 +1,180 @@
+use std::fmt;
+use chrono::NaiveDate;
+use crate::codes::TreeStandDataType;
+use crate::forest_property_data::{ForestPropertyData, StStand, TsTreeStandDataDate, TssTreeStandSummary, TstTreeStratum};
+use crate::values::Typed;
+
+// A TreeStandSummary derived from the tree strata of a TreeStandDataDate.
+// Basal area, stem count, volumes and biomass are sums over the strata; mean
+// age, diameter and height are weighted by basal area, or by stem count when
+// the strata have no basal area. Values are `None` when no stratum has them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DerivedSummary {
+    pub basal_area: Option<f64>,
+    pub stem_count: Option<u32>,
+    pub volume: Option<f64>,
+    pub saw_log_volume: Option<f64>,
+    pub pulp_wood_volume: Option<f64>,
+    pub volume_growth: Option<f64>,
+    pub mean_age: Option<f64>,
+    pub mean_diameter: Option<f64>,
+    pub mean_height: Option<f64>,
+    pub leaf_biomass: Option<f64>,
+    pub branch_biomass: Option<f64>,
+    pub stem_biomass: Option<f64>,
+    pub stump_biomass: Option<f64>,
+}
+
+impl DerivedSummary {
+    pub fn from_strata(strata: &[TstTreeStratum]) -> DerivedSummary {
+        let basal_area = sum(strata.iter().map(|stratum| value(&stratum.tst_basal_area)));
+        let weight = |stratum: &TstTreeStratum| match basal_area {
+            Some(_) => value(&stratum.tst_basal_area),
+            None => stratum.tst_stem_count.as_ref().map(|count| count.get() as f64),
+        };
+        let weighted_mean = |field: fn(&TstTreeStratum) -> Option<f64>| {
+            weighted_mean(strata.iter().filter_map(|stratum| Some((field(stratum)?, weight(stratum)?))))
+        };
+
+        DerivedSummary {
+            basal_area,
+            stem_count: strata.iter().filter_map(|stratum| stratum.tst_stem_count.as_ref()).map(|count| count.get()).reduce(|a, b| a + b),
+            volume: sum(strata.iter().map(|stratum| value(&stratum.tst_volume))),
+            saw_log_volume: sum(strata.iter().map(|stratum| value(&stratum.tst_saw_log_volume))),
+            pulp_wood_volume: sum(strata.iter().map(|stratum| value(&stratum.tst_pulp_wood_volume))),
+            volume_growth: sum(strata.iter().map(|stratum| value(&stratum.tst_volume_growth))),
+            mean_age: weighted_mean(|stratum| Some(stratum.tst_age.get() as f64)),
+            mean_diameter: weighted_mean(|stratum| value(&stratum.tst_mean_diameter)),
+            mean_height: weighted_mean(|stratum| Some(stratum.tst_mean_height.get())),
+            leaf_biomass: sum(strata.iter().map(|stratum| value(&stratum.tst_leaf_biomass))),
+            branch_biomass: sum(strata.iter().map(|stratum| value(&stratum.tst_branch_biomass))),
+            stem_biomass: sum(strata.iter().map(|stratum| value(&stratum.tst_stem_biomass))),
+            stump_biomass: sum(strata.iter().map(|stratum| value(&stratum.tst_stump_biomass))),
+        }
+    }
+
+    // Pairs of (summary field name, summary value, derived value) for the
+    // fields that have a value on both sides
+    fn compare(&self, summary: &TssTreeStandSummary) -> Vec<(&'static str, f64, f64)> {
+        let fields = [
+            ("BasalArea", Some(summary.tss_basal_area.get()), self.basal_area),
+            ("StemCount", Some(summary.tss_stem_count.get() as f64), self.stem_count.map(f64::from)),
+            ("Volume", Some(summary.tss_volume.get()), self.volume),
+            ("SawLogVolume", value(&summary.tss_saw_log_volume), self.saw_log_volume),
+            ("PulpWoodVolume", value(&summary.tss_pulp_wood_volume), self.pulp_wood_volume),
+            ("VolumeGrowth", Some(summary.tss_volume_growth.get()), self.volume_growth),
+            ("MeanAge", Some(summary.tss_mean_age.get() as f64), self.mean_age),
+            ("MeanDiameter", Some(summary.tss_mean_diameter.get()), self.mean_diameter),
+            ("MeanHeight", Some(summary.tss_mean_height.get()), self.mean_height),
+            ("LeafBiomass", value(&summary.tss_leaf_biomass), self.leaf_biomass),
+            ("BranchBiomass", value(&summary.tss_branch_biomass), self.branch_biomass),
+            ("StemBiomass", value(&summary.tss_stem_biomass), self.stem_biomass),
+            ("StumpBiomass", value(&summary.tss_stump_biomass), self.stump_biomass),
+        ];
+        fields.into_iter()
+            .filter_map(|(name, summary, derived)| Some((name, summary?, derived?)))
+            .collect()
+    }
+}
+
+impl TsTreeStandDataDate {
+    pub fn tree_strata(&self) -> &[TstTreeStratum] {
+        self.tst_tree_strata.as_ref().map(|strata| strata.tst_tree_stratum.as_slice()).unwrap_or_default()
+    }
+
+    // The summary derived from the tree strata, `None` if there are no strata
+    pub fn derived_summary(&self) -> Option<DerivedSummary> {
+        let strata = self.tree_strata();
+        (!strata.is_empty()).then(|| DerivedSummary::from_strata(strata))
+    }
+}
+
+// How far a TreeStandSummary value may be from the derived value. The summary
+// values are rounded in the exports, so a small absolute difference is always
+// allowed on top of the relative one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SummaryTolerance {
+    pub absolute: f64,
+    // Fraction of the derived value, e.g. 0.05 for 5 %
+    pub relative: f64,
+}
+
+impl Default for SummaryTolerance {
+    fn default() -> Self {
+        SummaryTolerance { absolute: 0.5, relative: 0.05 }
+    }
+}
+
+// A TreeStandSummary field that disagrees with the value derived from the strata
+#[derive(Debug, Clone, PartialEq)]
+pub struct SummaryDeviation {
+    pub stand_id: String,
+    pub date: NaiveDate,
+    pub data_type: TreeStandDataType,
+    // Element name in the summary, e.g. `BasalArea`
+    pub field: &'static str,
+    pub summary: f64,
+    pub derived: f64,
+}
+
+impl fmt::Display for SummaryDeviation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "stand {} {} (type {}): {} is {} in the summary, {:.2} from the strata",
+            self.stand_id,
+            self.date,
+            self.data_type.code(),
+            self.field,
+            self.summary,
+            self.derived
+        )
+    }
+}
+
+impl StStand {
+    // Compares every TreeStandSummary of the stand with the summary derived
+    // from the strata of the same date. Dates without strata are skipped.
+    pub fn check_summaries(&self, tolerance: SummaryTolerance) -> Vec<SummaryDeviation> {
+        let mut deviations = Vec::new();
+
+        for date in self.ts_tree_stand_data.iter().flat_map(|data| &data.ts_tree_stand_data_date) {
+            let (Some(summary), Some(derived)) = (&date.tss_tree_stand_summary, date.derived_summary()) else {
+                continue;
+            };
+            for (field, summary, derived) in derived.compare(summary) {
+                if (summary - derived).abs() > tolerance.absolute + tolerance.relative * derived.abs() {
+                    deviations.push(SummaryDeviation {
+                        stand_id: self.id.clone(),
+                        date: date.date.get(),
+                        data_type: date.ts_tree_stand_data_date_type.clone(),
+                        field,
+                        summary,
+                        derived,
+                    });
+                }
+            }
+        }
+
+        deviations
+    }
+}
+
+impl ForestPropertyData {
+    pub fn check_summaries(&self, tolerance: SummaryTolerance) -> Vec<SummaryDeviation> {
+        self.stands().flat_map(|stand| stand.check_summaries(tolerance)).collect()
+    }
+}
+
+fn value(field: &Option<Typed<f64>>) -> Option<f64> {
+    field.as_ref().map(|value| value.get())
+}
+
+fn sum(values: impl Iterator<Item = Option<f64>>) -> Option<f64> {
+    values.flatten().reduce(|a, b| a + b)
+}
+
+fn weighted_mean(values: impl Iterator<Item = (f64, f64)>) -> Option<f64> {
+    let (total, weights) = values.fold((0.0, 0.0), |(total, weights), (value, weight)| (total + value * weight, weights + weight));
+    (weights > 0.0).then(|| total / weights)
+}
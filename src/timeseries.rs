@@ -0,0 +1,109 @@
+use chrono::NaiveDate;
+use crate::codes::TreeStandDataType;
+use crate::forest_property_data::{StStand, TsTreeStandDataDate, TssTreeStandSummary};
+use crate::stand_summary::DerivedSummary;
+use crate::values::Typed;
+
+// Where the values of a data point were taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataPointSource {
+    Summary,
+    // Derived from the tree strata because the date has no TreeStandSummary
+    Strata,
+}
+
+// Biomass components as given in the data, per hectare
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Biomass {
+    pub leaf: Option<f64>,
+    pub branch: Option<f64>,
+    pub stem: Option<f64>,
+    pub stump: Option<f64>,
+}
+
+impl Biomass {
+    // Sum of the components that are present, `None` if none are
+    pub fn total(&self) -> Option<f64> {
+        [self.leaf, self.branch, self.stem, self.stump].into_iter().flatten().reduce(|a, b| a + b)
+    }
+}
+
+// The tree stand of one TreeStandDataDate. Values are per hectare, as in the data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StandDataPoint {
+    pub date: NaiveDate,
+    pub data_type: TreeStandDataType,
+    pub source: DataPointSource,
+    // m³/ha
+    pub volume: Option<f64>,
+    // m²/ha
+    pub basal_area: Option<f64>,
+    // m
+    pub mean_height: Option<f64>,
+    // stems/ha
+    pub stem_count: Option<u32>,
+    pub biomass: Biomass,
+}
+
+impl StStand {
+    // The development of the stand over time, one point per TreeStandDataDate
+    // ordered by date. Only the given data types are included, or all of them
+    // if `types` is empty.
+    pub fn time_series(&self, types: &[TreeStandDataType]) -> Vec<StandDataPoint> {
+        let mut points: Vec<_> = self.ts_tree_stand_data.iter()
+            .flat_map(|data| &data.ts_tree_stand_data_date)
+            .filter(|date| types.is_empty() || types.contains(&date.ts_tree_stand_data_date_type))
+            .map(data_point)
+            .collect();
+        points.sort_by_key(|point| point.date);
+        points
+    }
+}
+
+fn data_point(date: &TsTreeStandDataDate) -> StandDataPoint {
+    match &date.tss_tree_stand_summary {
+        Some(summary) => from_summary(date, summary),
+        None => from_strata(date),
+    }
+}
+
+fn from_summary(date: &TsTreeStandDataDate, summary: &TssTreeStandSummary) -> StandDataPoint {
+    StandDataPoint {
+        date: date.date.get(),
+        data_type: date.ts_tree_stand_data_date_type.clone(),
+        source: DataPointSource::Summary,
+        volume: Some(summary.tss_volume.get()),
+        basal_area: Some(summary.tss_basal_area.get()),
+        mean_height: Some(summary.tss_mean_height.get()),
+        stem_count: Some(summary.tss_stem_count.get()),
+        biomass: Biomass {
+            leaf: value(&summary.tss_leaf_biomass),
+            branch: value(&summary.tss_branch_biomass),
+            stem: value(&summary.tss_stem_biomass),
+            stump: value(&summary.tss_stump_biomass),
+        },
+    }
+}
+
+fn from_strata(date: &TsTreeStandDataDate) -> StandDataPoint {
+    let derived = DerivedSummary::from_strata(date.tree_strata());
+    StandDataPoint {
+        date: date.date.get(),
+        data_type: date.ts_tree_stand_data_date_type.clone(),
+        source: DataPointSource::Strata,
+        volume: derived.volume,
+        basal_area: derived.basal_area,
+        mean_height: derived.mean_height,
+        stem_count: derived.stem_count,
+        biomass: Biomass {
+            leaf: derived.leaf_biomass,
+            branch: derived.branch_biomass,
+            stem: derived.stem_biomass,
+            stump: derived.stump_biomass,
+        },
+    }
+}
+
+fn value(field: &Option<Typed<f64>>) -> Option<f64> {
+    field.as_ref().map(|value| value.get())
+}
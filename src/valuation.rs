@@ -0,0 +1,260 @@
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Deserializer, Serialize};
+use crate::codes::{InvalidCode, OperationType, StemType, TreeSpecies};
+use crate::error::ForestDataError;
+use crate::forest_property_data::{ForestPropertyData, StStand};
+use crate::harvest::AssortmentRemoval;
+
+// Stumpage price of a timber assortment in €/m³. A price without a species
+// applies to every species that has no price of its own.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimberPrice {
+    #[serde(default, deserialize_with = "optional_code", skip_serializing_if = "Option::is_none")]
+    pub species: Option<TreeSpecies>,
+    #[serde(deserialize_with = "code")]
+    pub stem_type: StemType,
+    pub price: f64,
+}
+
+// Prices for the real estates in the listed municipalities. Assortments
+// without a regional price use the prices of the table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegionPrices {
+    pub name: String,
+    #[serde(deserialize_with = "codes")]
+    pub municipalities: Vec<String>,
+    pub prices: Vec<TimberPrice>,
+}
+
+// A timber price table, read from TOML or JSON, e.g.
+//
+//   [[prices]]
+//   species = 1
+//   stem_type = 1
+//   price = 60.5
+//
+//   [[regions]]
+//   name = "Lappi"
+//   municipalities = ["698"]
+//   prices = [{ stem_type = 5, price = 15.0 }]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PriceTable {
+    #[serde(default)]
+    pub prices: Vec<TimberPrice>,
+    #[serde(default)]
+    pub regions: Vec<RegionPrices>,
+}
+
+impl PriceTable {
+    pub fn from_toml_str(toml: &str) -> Result<PriceTable, ForestDataError> {
+        toml::from_str(toml).map_err(|e| ForestDataError::PriceTable(e.to_string()))
+    }
+
+    pub fn from_json_str(json: &str) -> Result<PriceTable, ForestDataError> {
+        serde_json::from_str(json).map_err(|e| ForestDataError::PriceTable(e.to_string()))
+    }
+
+    // Reads a `.toml` file as TOML and anything else as JSON
+    pub fn from_file(path: &str) -> Result<PriceTable, ForestDataError> {
+        let text = fs::read_to_string(path)?;
+        match Path::new(path).extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("toml") => Self::from_toml_str(&text),
+            _ => Self::from_json_str(&text),
+        }
+    }
+
+    // The region of a municipality number, ignoring leading zeros
+    pub fn region(&self, municipality: Option<&str>) -> Option<&RegionPrices> {
+        let municipality = municipality?.trim().trim_start_matches('0');
+        if municipality.is_empty() {
+            return None;
+        }
+        self.regions.iter().find(|region| {
+            region.municipalities.iter().any(|code| code.trim().trim_start_matches('0') == municipality)
+        })
+    }
+
+    // €/m³ of an assortment, regional prices first
+    pub fn price(&self, species: &TreeSpecies, stem_type: &StemType, municipality: Option<&str>) -> Option<f64> {
+        let regional = self.region(municipality).map(|region| region.prices.as_slice()).unwrap_or_default();
+        find_price(regional, species, stem_type).or_else(|| find_price(&self.prices, species, stem_type))
+    }
+
+    // Value in € of assortment volumes in m³, and the volume that has no price
+    fn value_of(&self, removals: &[AssortmentRemoval], municipality: Option<&str>) -> (f64, f64) {
+        removals.iter().fold((0.0, 0.0), |(value, unpriced), removal| {
+            match self.price(&removal.species, &removal.stem_type, municipality) {
+                Some(price) => (value + price * removal.volume, unpriced),
+                None => (value, unpriced + removal.volume),
+            }
+        })
+    }
+}
+
+fn find_price(prices: &[TimberPrice], species: &TreeSpecies, stem_type: &StemType) -> Option<f64> {
+    let matching = |price: &&TimberPrice| &price.stem_type == stem_type;
+    prices.iter().filter(matching).find(|price| price.species.as_ref() == Some(species))
+        .or_else(|| prices.iter().filter(matching).find(|price| price.species.is_none()))
+        .map(|price| price.price)
+}
+
+// Codes may be written as numbers in the price table, e.g. `species = 1`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CodeValue {
+    Text(String),
+    Number(u64),
+}
+
+impl CodeValue {
+    fn into_string(self) -> String {
+        match self {
+            CodeValue::Text(text) => text,
+            CodeValue::Number(number) => number.to_string(),
+        }
+    }
+}
+
+fn code<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: for<'a> TryFrom<&'a str, Error = InvalidCode>,
+{
+    let code = CodeValue::deserialize(deserializer)?.into_string();
+    T::try_from(code.as_str()).map_err(serde::de::Error::custom)
+}
+
+fn optional_code<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<TreeSpecies>, D::Error> {
+    code(deserializer).map(Some)
+}
+
+fn codes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    Ok(Vec::<CodeValue>::deserialize(deserializer)?.into_iter().map(CodeValue::into_string).collect())
+}
+
+// Stumpage value of the proposed cuttings of one year and operation type
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CuttingValue {
+    pub year: u32,
+    pub operation_type: OperationType,
+    // m³
+    pub volume: f64,
+    // €
+    pub value: f64,
+}
+
+// Values of a stand in €. Volumes without a price in the table are left out
+// of the values and reported in `unpriced_volume`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StandValue {
+    pub stand_id: String,
+    pub area_ha: f64,
+    // The price region, `None` when the table prices were used
+    pub region: Option<String>,
+    // Saw log and pulp wood of the latest measured or updated tree stand data
+    pub standing_value: f64,
+    pub cuttings: Vec<CuttingValue>,
+    pub unpriced_volume: f64,
+}
+
+impl StandValue {
+    pub fn cutting_value(&self) -> f64 {
+        self.cuttings.iter().fold(0.0, |total, cutting| total + cutting.value)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PropertyValue {
+    pub stands: Vec<StandValue>,
+}
+
+// The totals are folded from 0.0 because summing f64s starts from -0.0, which
+// a document without stands would print as "-0.00"
+impl PropertyValue {
+    pub fn area_ha(&self) -> f64 {
+        self.stands.iter().fold(0.0, |total, stand| total + stand.area_ha)
+    }
+
+    pub fn standing_value(&self) -> f64 {
+        self.stands.iter().fold(0.0, |total, stand| total + stand.standing_value)
+    }
+
+    pub fn cutting_value(&self) -> f64 {
+        self.stands.iter().fold(0.0, |total, stand| total + stand.cutting_value())
+    }
+
+    pub fn unpriced_volume(&self) -> f64 {
+        self.stands.iter().fold(0.0, |total, stand| total + stand.unpriced_volume)
+    }
+
+    // Stumpage value of the proposed cuttings of all stands by year
+    pub fn cutting_value_by_year(&self) -> Vec<(u32, f64)> {
+        let mut years: Vec<(u32, f64)> = Vec::new();
+        for cutting in self.stands.iter().flat_map(|stand| &stand.cuttings) {
+            match years.iter_mut().find(|(year, _)| *year == cutting.year) {
+                Some((_, value)) => *value += cutting.value,
+                None => years.push((cutting.year, cutting.value)),
+            }
+        }
+        years.sort_by_key(|(year, _)| *year);
+        years
+    }
+}
+
+impl StStand {
+    // `municipality` is the MunicipalityNumber of the real estate of the
+    // stand, used to pick the price region
+    pub fn value(&self, prices: &PriceTable, municipality: Option<&str>) -> StandValue {
+        let mut unpriced_volume = 0.0;
+
+        let standing: Vec<_> = self.volume_breakdown().species.into_iter()
+            .flat_map(|species| [
+                AssortmentRemoval { species: species.species.clone(), stem_type: StemType::SawLog, volume: species.saw_log_volume },
+                AssortmentRemoval { species: species.species, stem_type: StemType::PulpWood, volume: species.pulp_wood_volume },
+            ])
+            .collect();
+        let (standing_value, unpriced) = prices.value_of(&standing, municipality);
+        unpriced_volume += unpriced;
+
+        let mut cuttings = Vec::new();
+        for group in self.harvest_plan().groups {
+            // Removal that is not divided into assortments has no price
+            let (value, unpriced) = prices.value_of(&group.assortments, municipality);
+            let assortment_volume: f64 = group.assortments.iter().map(|removal| removal.volume).sum();
+            unpriced_volume += unpriced + (group.volume - assortment_volume).max(0.0);
+            cuttings.push(CuttingValue {
+                year: group.year,
+                operation_type: group.operation_type,
+                volume: group.volume,
+                value,
+            });
+        }
+
+        StandValue {
+            stand_id: self.id.clone(),
+            area_ha: self.st_stand_basic_data.st_area.get(),
+            region: prices.region(municipality).map(|region| region.name.clone()),
+            standing_value,
+            cuttings,
+            unpriced_volume,
+        }
+    }
+}
+
+impl ForestPropertyData {
+    // Values of every stand in the document. Stands outside real estates
+    // have no municipality and are valued with the table prices.
+    pub fn value(&self, prices: &PriceTable) -> PropertyValue {
+        let mut stands: Vec<_> = self.st_stands.iter()
+            .flat_map(|stands| &stands.st_stand)
+            .map(|stand| stand.value(prices, None))
+            .collect();
+        for estate in self.real_estates() {
+            for stand in estate.re_parcels.re_parcel.iter().flat_map(|parcel| &parcel.st_stands.st_stand) {
+                stands.push(stand.value(prices, Some(&estate.re_municipality_number)));
+            }
+        }
+        PropertyValue { stands }
+    }
+}
@@ -0,0 +1,171 @@
+use std::cell::Cell;
+use std::fmt;
+use std::marker::PhantomData;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+// Conversion between a Rust type and its lexical form in the XML
+pub trait XmlText: Sized {
+    fn parse_text(text: &str) -> Result<Self, String>;
+    fn to_text(&self) -> String;
+
+    // A text that is equal for equal values, used to compare documents
+    fn canonical_text(&self) -> String {
+        self.to_text()
+    }
+}
+
+impl XmlText for f64 {
+    fn parse_text(text: &str) -> Result<Self, String> {
+        text.parse().map_err(|e| format!("invalid decimal `{}`: {}", text, e))
+    }
+
+    fn to_text(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl XmlText for u32 {
+    fn parse_text(text: &str) -> Result<Self, String> {
+        text.parse().map_err(|e| format!("invalid integer `{}`: {}", text, e))
+    }
+
+    fn to_text(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl XmlText for NaiveDate {
+    fn parse_text(text: &str) -> Result<Self, String> {
+        NaiveDate::parse_from_str(text, DATE_FORMAT).map_err(|e| format!("invalid date `{}`: {}", text, e))
+    }
+
+    fn to_text(&self) -> String {
+        self.format(DATE_FORMAT).to_string()
+    }
+}
+
+impl XmlText for NaiveDateTime {
+    fn parse_text(text: &str) -> Result<Self, String> {
+        // Fractional seconds are accepted on input
+        text.parse().map_err(|e| format!("invalid date and time `{}`: {}", text, e))
+    }
+
+    fn to_text(&self) -> String {
+        self.format(DATE_TIME_FORMAT).to_string()
+    }
+
+    fn canonical_text(&self) -> String {
+        self.format("%Y-%m-%dT%H:%M:%S%.f").to_string()
+    }
+}
+
+// A typed field value that remembers the text it was parsed from.
+// Serializing writes the original text back, so an unmodified document
+// round-trips without reformatting numbers or dates.
+#[derive(Clone, Debug)]
+pub struct Typed<T> {
+    value: T,
+    text: String,
+}
+
+impl<T: XmlText> Typed<T> {
+    pub fn new(value: T) -> Self {
+        let text = value.to_text();
+        Typed { value, text }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let value = T::parse_text(text.trim())?;
+        Ok(Typed { value, text: text.to_string() })
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    // The text the value is serialized as
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set(&mut self, value: T) {
+        self.text = value.to_text();
+        self.value = value;
+    }
+}
+
+impl<T: XmlText + Copy> Typed<T> {
+    pub fn get(&self) -> T {
+        self.value
+    }
+}
+
+impl<T: XmlText> From<T> for Typed<T> {
+    fn from(value: T) -> Self {
+        Typed::new(value)
+    }
+}
+
+impl<T: PartialEq> PartialEq for Typed<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> fmt::Display for Typed<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+thread_local! {
+    static CANONICAL: Cell<bool> = const { Cell::new(false) };
+}
+
+// Runs `f` with typed values serialized as their canonical text instead of
+// the original text, so that e.g. "5.2" and "5.20" serialize the same
+pub(crate) fn with_canonical_text<R>(f: impl FnOnce() -> R) -> R {
+    struct Reset(bool);
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            CANONICAL.set(self.0);
+        }
+    }
+
+    let _reset = Reset(CANONICAL.replace(true));
+    f()
+}
+
+impl<T: XmlText> Serialize for Typed<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if CANONICAL.get() {
+            serializer.serialize_str(&self.value.canonical_text())
+        } else {
+            serializer.serialize_str(&self.text)
+        }
+    }
+}
+
+impl<'de, T: XmlText> Deserialize<'de> for Typed<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(TypedVisitor(PhantomData))
+    }
+}
+
+struct TypedVisitor<T>(PhantomData<T>);
+
+impl<T: XmlText> de::Visitor<'_> for TypedVisitor<T> {
+    type Value = Typed<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a typed value as text")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Typed::parse(v).map_err(E::custom)
+    }
+}
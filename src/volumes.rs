@@ -0,0 +1,129 @@
+use crate::codes::TreeSpecies;
+use crate::forest_property_data::{ForestPropertyData, ReParcel, ReRealEstate, StStand, TstTreeStratum};
+
+// Growing stock of one tree species in m³, or m³/ha in a per-hectare breakdown
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeciesVolume {
+    pub species: TreeSpecies,
+    pub volume: f64,
+    pub saw_log_volume: f64,
+    pub pulp_wood_volume: f64,
+}
+
+// Growing stock of a stand or a group of stands by tree species and timber
+// assortment, from the latest measured or updated tree stand data of each stand
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VolumeBreakdown {
+    // Area of all the stands, including those without tree stand data
+    pub area_ha: f64,
+    // Ordered by tree species code
+    pub species: Vec<SpeciesVolume>,
+}
+
+impl VolumeBreakdown {
+    pub fn from_stands<'a>(stands: impl IntoIterator<Item = &'a StStand>) -> VolumeBreakdown {
+        let mut breakdown = VolumeBreakdown::default();
+        for stand in stands {
+            breakdown.add_stand(stand);
+        }
+        breakdown.species.sort_by_key(|species| species_order(&species.species));
+        breakdown
+    }
+
+    fn add_stand(&mut self, stand: &StStand) {
+        let area_ha = stand.st_stand_basic_data.st_area.get();
+        self.area_ha += area_ha;
+
+        let strata = stand.current_tree_stand_data().map(|date| date.tree_strata()).unwrap_or_default();
+        for stratum in strata {
+            let index = match self.species.iter().position(|species| species.species == stratum.tst_tree_species) {
+                Some(index) => index,
+                None => {
+                    self.species.push(SpeciesVolume {
+                        species: stratum.tst_tree_species.clone(),
+                        volume: 0.0,
+                        saw_log_volume: 0.0,
+                        pulp_wood_volume: 0.0,
+                    });
+                    self.species.len() - 1
+                }
+            };
+            let (volume, saw_log_volume, pulp_wood_volume) = stratum_volumes(stratum);
+            let species = &mut self.species[index];
+            species.volume += volume * area_ha;
+            species.saw_log_volume += saw_log_volume * area_ha;
+            species.pulp_wood_volume += pulp_wood_volume * area_ha;
+        }
+    }
+
+    pub fn volume(&self) -> f64 {
+        self.species.iter().fold(0.0, |total, species| total + species.volume)
+    }
+
+    pub fn saw_log_volume(&self) -> f64 {
+        self.species.iter().fold(0.0, |total, species| total + species.saw_log_volume)
+    }
+
+    pub fn pulp_wood_volume(&self) -> f64 {
+        self.species.iter().fold(0.0, |total, species| total + species.pulp_wood_volume)
+    }
+
+    // The same breakdown in m³/ha over `area_ha`
+    pub fn per_hectare(&self) -> VolumeBreakdown {
+        let divisor = if self.area_ha > 0.0 { self.area_ha } else { 1.0 };
+        VolumeBreakdown {
+            area_ha: self.area_ha,
+            species: self.species.iter()
+                .map(|species| SpeciesVolume {
+                    species: species.species.clone(),
+                    volume: species.volume / divisor,
+                    saw_log_volume: species.saw_log_volume / divisor,
+                    pulp_wood_volume: species.pulp_wood_volume / divisor,
+                })
+                .collect(),
+        }
+    }
+}
+
+// Volume, saw log volume and pulp wood volume of a stratum in m³/ha. A missing
+// SawLogVolume is taken from SawLogPercent when the stratum has one.
+fn stratum_volumes(stratum: &TstTreeStratum) -> (f64, f64, f64) {
+    let volume = stratum.tst_volume.as_ref().map_or(0.0, |volume| volume.get());
+    let saw_log_volume = match (&stratum.tst_saw_log_volume, &stratum.tst_saw_log_percent) {
+        (Some(saw_log), _) => saw_log.get(),
+        (None, Some(percent)) => volume * percent.get() / 100.0,
+        (None, None) => 0.0,
+    };
+    let pulp_wood_volume = stratum.tst_pulp_wood_volume.as_ref().map_or(0.0, |volume| volume.get());
+    (volume, saw_log_volume, pulp_wood_volume)
+}
+
+// Numeric codes in order, unlisted codes after them
+fn species_order(species: &TreeSpecies) -> (u32, String) {
+    (species.code().parse().unwrap_or(u32::MAX), species.code().to_string())
+}
+
+impl StStand {
+    pub fn volume_breakdown(&self) -> VolumeBreakdown {
+        VolumeBreakdown::from_stands([self])
+    }
+}
+
+impl ReParcel {
+    pub fn volume_breakdown(&self) -> VolumeBreakdown {
+        VolumeBreakdown::from_stands(&self.st_stands.st_stand)
+    }
+}
+
+impl ReRealEstate {
+    pub fn volume_breakdown(&self) -> VolumeBreakdown {
+        VolumeBreakdown::from_stands(self.re_parcels.re_parcel.iter().flat_map(|parcel| &parcel.st_stands.st_stand))
+    }
+}
+
+impl ForestPropertyData {
+    // Breakdown of every stand in the document
+    pub fn volume_breakdown(&self) -> VolumeBreakdown {
+        VolumeBreakdown::from_stands(self.stands())
+    }
+}
@@ -0,0 +1,105 @@
+use std::cell::Cell;
+use std::fs;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use quick_xml::se::to_string;
+use crate::error::ForestDataError;
+use crate::forest_property_data::ForestPropertyData;
+use crate::namespaces::{apply_prefixes, PrefixStyle};
+
+thread_local! {
+    // Set while the model is serialized as XML, where the header comments are
+    // written before the root element instead of as a field
+    static WRITING_XML: Cell<bool> = const { Cell::new(false) };
+}
+
+const PARSER_COMMENT: &str = concat!("Parsed with forestry_xml_parser V", env!("CARGO_PKG_VERSION"));
+
+// Options for writing a ForestPropertyData document as XML
+#[derive(Debug, Clone)]
+pub struct XmlWriteOptions {
+    pub prefix_style: PrefixStyle,
+    // Encoding label written in the XML declaration, e.g. "utf-8" or "iso-8859-1"
+    pub encoding: String,
+    // Number of spaces per nesting level, or `None` for a single line
+    pub indent: Option<usize>,
+    // Write the header comments of the source document and a parser comment
+    pub header_comments: bool,
+}
+
+impl Default for XmlWriteOptions {
+    fn default() -> Self {
+        XmlWriteOptions {
+            prefix_style: PrefixStyle::Metsakeskus,
+            encoding: "utf-8".to_string(),
+            indent: Some(2),
+            header_comments: true,
+        }
+    }
+}
+
+impl ForestPropertyData {
+    // Writes the data as a complete XML document encoded as declared in `opts`
+    pub fn to_xml(&self, opts: &XmlWriteOptions) -> Result<Vec<u8>, ForestDataError> {
+        let encoding = Encoding::for_label(opts.encoding.as_bytes())
+            .ok_or_else(|| ForestDataError::Encoding(format!("Unsupported encoding: {}", opts.encoding)))?;
+
+        let body = serialize_xml(self)?;
+        let body = apply_prefixes(&body, &opts.prefix_style, opts.indent)?;
+
+        let mut xml = format!("<?xml version=\"1.0\" encoding=\"{}\"?>\n", opts.encoding);
+        if opts.header_comments {
+            // Comments from an earlier run of this parser are replaced by the current one
+            for comment in self.header_comments.iter().filter(|c| !c.starts_with("Parsed with forestry_xml_parser")) {
+                xml.push_str(&format!("<!--{}-->\n", comment));
+            }
+            xml.push_str(&format!("<!--{}-->\n", PARSER_COMMENT));
+        }
+        xml.push_str(&body);
+        xml.push('\n');
+
+        Ok(encode(&xml, encoding))
+    }
+
+    pub fn write_xml_file(&self, path: &str, opts: &XmlWriteOptions) -> Result<(), ForestDataError> {
+        fs::write(path, self.to_xml(opts)?)?;
+        Ok(())
+    }
+}
+
+pub(crate) fn serialize_xml(data: &ForestPropertyData) -> Result<String, ForestDataError> {
+    struct Reset(bool);
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            WRITING_XML.set(self.0);
+        }
+    }
+
+    let _reset = Reset(WRITING_XML.replace(true));
+    to_string(data).map_err(|e| ForestDataError::Serialization(e.to_string()))
+}
+
+pub(crate) fn skip_header_comments(comments: &[String]) -> bool {
+    comments.is_empty() || WRITING_XML.get()
+}
+
+// Characters the target encoding cannot represent are written as character references
+fn encode(xml: &str, encoding: &'static Encoding) -> Vec<u8> {
+    if encoding == UTF_16LE || encoding == UTF_16BE {
+        let big_endian = encoding == UTF_16BE;
+        return std::iter::once('\u{feff}')
+            .chain(xml.chars())
+            .flat_map(|c| {
+                let mut units = [0u16; 2];
+                c.encode_utf16(&mut units).to_vec()
+            })
+            .flat_map(|unit| if big_endian { unit.to_be_bytes() } else { unit.to_le_bytes() })
+            .collect();
+    }
+
+    if encoding == UTF_8 {
+        return xml.as_bytes().to_vec();
+    }
+
+    let (bytes, _, _) = encoding.encode(xml);
+    bytes.into_owned()
+}
@@ -0,0 +1,71 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use forestry_xml_parser::batch::{convert_directory, BatchFormat, BatchOptions};
+
+// Copies the sample stand files and one broken file into a fresh directory
+fn input_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("forestry_xml_parser_batch_{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("nested")).unwrap();
+
+    fs::copy("xml_stands/XML_MV_L5121E.xml", dir.join("XML_MV_L5121E.xml")).unwrap();
+    fs::copy("xml_stands/XML_MV_V4314F.xml", dir.join("nested/XML_MV_V4314F.xml")).unwrap();
+    fs::write(dir.join("nested/broken.xml"), "<ForestPropertyData><Stands>").unwrap();
+    fs::write(dir.join("notes.txt"), "not xml").unwrap();
+    dir
+}
+
+fn names(paths: &[&Path]) -> Vec<String> {
+    paths.iter().map(|p| p.file_name().unwrap().to_string_lossy().to_string()).collect()
+}
+
+#[test]
+fn failures_are_reported_without_stopping_the_batch() {
+    let dir = input_dir("alongside");
+    let report = convert_directory(&dir, &BatchOptions::default()).unwrap();
+
+    assert_eq!(report.total(), 3);
+    assert!(!report.is_success());
+    assert_eq!(names(&report.succeeded.iter().map(|s| s.output.as_path()).collect::<Vec<_>>()), ["XML_MV_L5121E.json", "XML_MV_V4314F.json"]);
+    assert!(dir.join("nested/XML_MV_V4314F.json").exists());
+
+    assert_eq!(report.failed.len(), 1);
+    assert!(report.failed[0].input.ends_with("nested/broken.xml"));
+    assert!(report.to_string().contains("broken.xml: "), "{}", report);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn outputs_can_go_into_a_mirror_tree() {
+    let dir = input_dir("mirror");
+    let output_dir = dir.with_extension("out");
+    let _ = fs::remove_dir_all(&output_dir);
+
+    let opts = BatchOptions { format: BatchFormat::GeoJson, output_dir: Some(output_dir.clone()), ..BatchOptions::default() };
+    let report = convert_directory(&dir, &opts).unwrap();
+
+    assert_eq!(report.succeeded.len(), 2);
+    let geojson: serde_json::Value = serde_json::from_slice(&fs::read(output_dir.join("nested/XML_MV_V4314F.geojson")).unwrap()).unwrap();
+    assert_eq!(geojson["type"], "FeatureCollection");
+    assert!(!dir.join("XML_MV_L5121E.geojson").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+    fs::remove_dir_all(&output_dir).unwrap();
+}
+
+#[test]
+fn xml_output_never_overwrites_the_input() {
+    let dir = input_dir("xml");
+    let original = fs::read(dir.join("XML_MV_L5121E.xml")).unwrap();
+
+    // The whole batch is refused instead of failing every file
+    let opts = BatchOptions { format: BatchFormat::Xml, ..BatchOptions::default() };
+    assert!(convert_directory(&dir, &opts).is_err());
+
+    let opts = BatchOptions { output_dir: Some(dir.clone()), ..opts };
+    assert!(convert_directory(&dir, &opts).is_err());
+    assert_eq!(fs::read(dir.join("XML_MV_L5121E.xml")).unwrap(), original);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
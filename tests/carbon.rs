@@ -0,0 +1,69 @@
+use forestry_xml_parser::carbon::CarbonFactors;
+use forestry_xml_parser::codes::{TreeSpecies, TreeStandDataType};
+
+mod common;
+
+use common::{approx, history_stand, load, HISTORY};
+
+#[test]
+fn history_uses_biomass_and_estimates_from_volume() {
+    let factors = CarbonFactors::default();
+    let history = history_stand("29727379").carbon_history(&factors, &[]);
+    assert_eq!(history.len(), 3);
+
+    // 2017: strata without biomass, estimated from volume by species
+    let measured = &history[0];
+    let per_ha = 1.3 * 0.66 + 0.6 * 0.76 + 0.3 * 0.75;
+    assert!(approx(measured.stock.biomass, per_ha * 5.2));
+    assert!(approx(measured.stock.estimated_biomass, measured.stock.biomass));
+    assert_eq!(measured.co2_change, None);
+
+    // 2020: strata with all four biomass components
+    let updated = &history[1];
+    let per_ha = (0.03 + 0.08 + 0.20 + 0.06) + (0.08 + 0.11 + 0.15 + 0.29) + (0.00 + 0.13 + 0.23 + 0.32);
+    assert!(approx(updated.stock.biomass, per_ha * 5.2));
+    assert_eq!(updated.stock.estimated_biomass, 0.0);
+    assert!(approx(updated.stock.carbon, updated.stock.biomass * 0.5));
+    assert!(approx(updated.stock.co2, updated.stock.carbon * 44.0 / 12.0));
+    assert!(approx(updated.co2_change.unwrap(), updated.stock.co2 - measured.stock.co2));
+
+    // 2030: a summary without strata or biomass, main tree species 29
+    let forecast = &history[2];
+    assert_eq!(forecast.data_type, TreeStandDataType::Forecast);
+    assert!(approx(forecast.stock.biomass, 4.0 * 0.75 * 5.2));
+}
+
+#[test]
+fn current_stock_skips_forecasts() {
+    let stand = history_stand("29727379");
+    let factors = CarbonFactors::default();
+    let history = stand.carbon_history(&factors, &[TreeStandDataType::Measured, TreeStandDataType::Updated]);
+    assert_eq!(history.len(), 2);
+    assert_eq!(stand.carbon_stock(&factors), history[1].stock);
+}
+
+#[test]
+fn factors_are_configurable() {
+    let factors = CarbonFactors {
+        carbon_fraction: 0.47,
+        expansion_factors: vec![(TreeSpecies::ScotsPine, 1.0)],
+        default_expansion_factor: 0.5,
+        ..CarbonFactors::default()
+    };
+    let measured = &history_stand("29727379").carbon_history(&factors, &[TreeStandDataType::Measured])[0];
+    let biomass = (1.3 * 1.0 + 0.6 * 0.5 + 0.3 * 0.5) * 5.2;
+    assert!(approx(measured.stock.biomass, biomass));
+    assert!(approx(measured.stock.carbon, biomass * 0.47));
+}
+
+#[test]
+fn property_stock_is_the_sum_of_stands() {
+    let data = load(HISTORY);
+    let factors = CarbonFactors::default();
+    let total = data.carbon_stock(&factors);
+
+    let co2: f64 = data.stands().map(|stand| stand.carbon_stock(&factors).co2).sum();
+    assert!((total.co2 - co2).abs() < 1e-6);
+    assert!((total.area_ha - 44.59).abs() < 0.005);
+    assert!(total.estimated_biomass < total.biomass);
+}
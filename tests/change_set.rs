@@ -0,0 +1,229 @@
+use chrono::NaiveDateTime;
+use forestry_xml_parser::codes::ChangeState;
+use forestry_xml_parser::forest_property_data::{ForestPropertyData, StStand};
+use forestry_xml_parser::values::Typed;
+use forestry_xml_parser::{ForestDataError, XmlWriteOptions};
+use serde_json::Value;
+
+const HISTORY: &str = "xml_history/XML_MV_K3421F.xml";
+const ORIG: &str = "orig_forestpropertydata.xml";
+
+fn load(file: &str) -> ForestPropertyData {
+    ForestPropertyData::try_from_xml_file(file).unwrap()
+}
+
+fn time() -> NaiveDateTime {
+    "2024-05-02T10:30:00".parse().unwrap()
+}
+
+fn stand<'a>(data: &'a ForestPropertyData, id: &str) -> &'a StStand {
+    data.stands().find(|s| s.id == id).unwrap()
+}
+
+fn stand_mut<'a>(data: &'a mut ForestPropertyData, id: &str) -> &'a mut StStand {
+    data.stands_mut().find(|s| s.id == id).unwrap()
+}
+
+// The document as JSON without ChangeState and ChangeTime
+fn content(data: &ForestPropertyData) -> Value {
+    fn strip(value: &mut Value) {
+        match value {
+            Value::Object(object) => {
+                object.remove("ChangeState");
+                object.remove("ChangeTime");
+                object.values_mut().for_each(strip);
+            }
+            Value::Array(entries) => entries.iter_mut().for_each(strip),
+            _ => {}
+        }
+    }
+    let mut value = serde_json::to_value(data).unwrap();
+    strip(&mut value);
+    value
+}
+
+// Stand 29727379 with a new area, a changed stratum and a removed special
+// feature, stand 29727381 removed and a copy of stand 29727382 added
+fn edited_history(base: &ForestPropertyData) -> ForestPropertyData {
+    let mut edited = base.clone();
+    let changed = stand_mut(&mut edited, "29727379");
+    changed.st_stand_basic_data.st_area = Typed::parse("5.10").unwrap();
+    let strata = &mut changed.ts_tree_stand_data.as_mut().unwrap().ts_tree_stand_data_date[0];
+    strata.tst_tree_strata.as_mut().unwrap().tst_tree_stratum[0].tst_volume = Some(Typed::parse("1.5").unwrap());
+    changed.st_special_features.as_mut().unwrap().st_special_feature.retain(|f| f.id != "10864231");
+
+    let stands = &mut edited.st_stands.as_mut().unwrap().st_stand;
+    stands.retain(|s| s.id != "29727381");
+    let mut added = stands.iter().find(|s| s.id == "29727382").unwrap().clone();
+    added.id = "1".to_string();
+    stands.push(added);
+    edited
+}
+
+// Operation 1194510 of stand 2553941 without its first assortment, and a
+// copy of it added as operation 9
+fn edited_operations(base: &ForestPropertyData) -> ForestPropertyData {
+    let mut edited = base.clone();
+    let operations = &mut stand_mut(&mut edited, "2553941").op_operations.as_mut().unwrap().op_operation;
+    let operation = operations.iter_mut().find(|o| o.id == "1194510").unwrap();
+    operation.op_cutting.as_mut().unwrap().op_assortments.as_mut().unwrap().op_assortment.remove(0);
+    let mut added = operation.clone();
+    added.id = "9".to_string();
+    operations.push(added);
+    edited
+}
+
+#[test]
+fn unchanged_document_gives_an_empty_change_set() {
+    let base = load(ORIG);
+    let changes = base.change_set(&base, time()).unwrap();
+    assert_eq!(changes.stands().count(), 0);
+    assert!(changes.re_real_estates.is_none());
+    assert_eq!(changes.header_comments, base.header_comments);
+}
+
+#[test]
+fn reformatted_values_are_not_changes() {
+    let base = load(HISTORY);
+    let mut edited = base.clone();
+    stand_mut(&mut edited, "29727379").st_stand_basic_data.st_area = Typed::parse("5.2").unwrap();
+    let changes = base.change_set(&edited, time()).unwrap();
+    assert_eq!(changes.stands().count(), 0);
+}
+
+#[test]
+fn changed_elements_are_marked() {
+    let base = load(HISTORY);
+    let changes = base.change_set(&edited_history(&base), time()).unwrap();
+
+    let ids: Vec<_> = changes.stands().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, ["29727379", "1", "29727381"]);
+
+    let state = |id: &str| stand(&changes, id).st_stand_basic_data.co_change_state.clone();
+    assert_eq!(state("29727379"), Some(ChangeState::Updated));
+    assert_eq!(state("1"), Some(ChangeState::Inserted));
+    assert_eq!(state("29727381"), Some(ChangeState::Deleted));
+
+    let changed = stand(&changes, "29727379");
+    assert_eq!(changed.st_stand_basic_data.co_change_time.as_ref().unwrap().get(), time());
+    let strata = &changed.ts_tree_stand_data.as_ref().unwrap().ts_tree_stand_data_date[0].tst_tree_strata.as_ref().unwrap().tst_tree_stratum;
+    assert_eq!(strata[0].co_change_state, Some(ChangeState::Updated));
+    assert_eq!(strata[0].co_change_time.as_ref().unwrap().get(), time());
+    assert_eq!(strata[1].co_change_state, Some(ChangeState::Unchanged));
+    assert!(strata[1].co_change_time.is_none());
+
+    let features = &changed.st_special_features.as_ref().unwrap().st_special_feature;
+    let removed = features.iter().find(|f| f.id == "10864231").unwrap();
+    assert_eq!(removed.co_change_state, Some(ChangeState::Deleted));
+}
+
+#[test]
+fn operations_and_assortments_are_marked() {
+    let base = load(ORIG);
+    let changes = base.change_set(&edited_operations(&base), time()).unwrap();
+
+    let estate = changes.real_estates().next().unwrap();
+    assert_eq!(estate.id, "526637");
+    assert_eq!(estate.re_parcels.re_parcel.len(), 1);
+
+    let changed = stand(&changes, "2553941");
+    assert_eq!(changed.st_stand_basic_data.co_change_state, Some(ChangeState::Unchanged));
+    let operations = &changed.op_operations.as_ref().unwrap().op_operation;
+    let updated = operations.iter().find(|o| o.id == "1194510").unwrap();
+    assert_eq!(updated.co_change_state, Some(ChangeState::Updated));
+    let assortments = &updated.op_cutting.as_ref().unwrap().op_assortments.as_ref().unwrap().op_assortment;
+    assert_eq!(assortments.last().unwrap().co_change_state, Some(ChangeState::Deleted));
+
+    let inserted = operations.iter().find(|o| o.id == "9").unwrap();
+    assert_eq!(inserted.co_change_state, Some(ChangeState::Inserted));
+    let assortments = &inserted.op_cutting.as_ref().unwrap().op_assortments.as_ref().unwrap().op_assortment;
+    assert!(assortments.iter().all(|a| a.co_change_state == Some(ChangeState::Inserted)));
+}
+
+#[test]
+fn applying_a_change_set_gives_the_edited_document() {
+    for (file, edit) in [(HISTORY, edited_history as fn(&ForestPropertyData) -> ForestPropertyData), (ORIG, edited_operations)] {
+        let mut base = load(file);
+        let edited = edit(&base);
+        let changes = base.change_set(&edited, time()).unwrap();
+
+        // Through XML, as a change-set would be sent
+        let xml = String::from_utf8(changes.to_xml(&XmlWriteOptions::default()).unwrap()).unwrap();
+        assert!(xml.contains("<co:ChangeState>3</co:ChangeState>"));
+        let received = ForestPropertyData::try_from_xml_str(&xml).unwrap();
+
+        base.apply_change_set(&received).unwrap();
+        assert!(content(&base) == content(&edited), "{}", file);
+    }
+}
+
+// Stand 29727379 with the volume of the 2020 summary changed, a summary-only
+// date added for 2035 and the summary-only 2030 date removed
+fn edited_summaries(base: &ForestPropertyData) -> ForestPropertyData {
+    let mut edited = base.clone();
+    let dates = &mut stand_mut(&mut edited, "29727379").ts_tree_stand_data.as_mut().unwrap().ts_tree_stand_data_date;
+    dates[1].tss_tree_stand_summary.as_mut().unwrap().tss_volume = Typed::parse("180.5").unwrap();
+    let mut added = dates.remove(2);
+    added.date = Typed::parse("2035-01-01").unwrap();
+    added.tss_tree_stand_summary.as_mut().unwrap().id = "2".to_string();
+    dates.push(added);
+    edited
+}
+
+#[test]
+fn tree_stand_summaries_are_marked() {
+    let base = load(HISTORY);
+    let changes = base.change_set(&edited_summaries(&base), time()).unwrap();
+
+    let ids: Vec<_> = changes.stands().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, ["29727379"]);
+    let dates = &stand(&changes, "29727379").ts_tree_stand_data.as_ref().unwrap().ts_tree_stand_data_date;
+    let summary = |date: &str| {
+        let date = dates.iter().find(|d| d.date.text() == date).unwrap();
+        let summary = date.tss_tree_stand_summary.as_ref().unwrap();
+        (summary.co_change_state.clone(), summary.co_change_time.as_ref().map(|t| t.get()))
+    };
+    assert_eq!(summary("2020-01-01"), (Some(ChangeState::Updated), Some(time())));
+    assert_eq!(summary("2035-01-01"), (Some(ChangeState::Inserted), Some(time())));
+    assert_eq!(summary("2030-01-01"), (Some(ChangeState::Deleted), Some(time())));
+    assert!(dates.iter().find(|d| d.date.text() == "2017-06-22").unwrap().tss_tree_stand_summary.is_none());
+}
+
+#[test]
+fn applying_summary_changes_gives_the_edited_document() {
+    let mut base = load(HISTORY);
+    let edited = edited_summaries(&base);
+    let changes = base.change_set(&edited, time()).unwrap();
+    let xml = String::from_utf8(changes.to_xml(&XmlWriteOptions::default()).unwrap()).unwrap();
+    let received = ForestPropertyData::try_from_xml_str(&xml).unwrap();
+
+    base.apply_change_set(&received).unwrap();
+    assert!(content(&base) == content(&edited));
+    let dates = &stand(&base, "29727379").ts_tree_stand_data.as_ref().unwrap().ts_tree_stand_data_date;
+    assert!(!dates.iter().any(|d| d.date.text() == "2030-01-01"));
+}
+
+#[test]
+fn updating_a_missing_stand_fails() {
+    let base = load(HISTORY);
+    let changes = base.change_set(&edited_history(&base), time()).unwrap();
+
+    let mut other = load("xml_history/XML_MV_L3132A.xml");
+    let result = other.apply_change_set(&changes);
+    assert!(matches!(result, Err(ForestDataError::ChangeSet(msg)) if msg.contains("29727379")));
+}
+
+#[test]
+fn failed_change_set_leaves_the_document_unchanged() {
+    let base = load(HISTORY);
+    let mut changes = base.change_set(&edited_history(&base), time()).unwrap();
+    // The deleted stand 29727381 first, so it would be removed before the
+    // missing stand is found
+    changes.st_stands.as_mut().unwrap().st_stand.rotate_right(1);
+
+    let mut other = base.clone();
+    other.st_stands.as_mut().unwrap().st_stand.retain(|s| s.id != "29727379");
+    let before = content(&other);
+    assert!(other.apply_change_set(&changes).is_err());
+    assert!(content(&other) == before);
+}
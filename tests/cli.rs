@@ -0,0 +1,203 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run(args: &[&str], stdin: Option<&[u8]>) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_forestry_xml_parser"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    if let Some(input) = stdin {
+        child.stdin.take().unwrap().write_all(input).unwrap();
+    }
+    drop(child.stdin.take());
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn json_round_trip_through_stdin_and_stdout() {
+    let json = run(&["to-json", "xml_stands/XML_MV_V4314F.xml", "--compact"], None);
+    assert!(json.status.success());
+    assert!(!json.stdout.contains(&b'\n'));
+
+    let xml = run(&["to-xml", "-", "--prefixes", "bare"], Some(&json.stdout));
+    assert!(xml.status.success(), "{}", String::from_utf8_lossy(&xml.stderr));
+    let xml = String::from_utf8(xml.stdout).unwrap();
+    assert!(xml.contains("<Stand id=\"37339713\">"));
+
+    let validated = run(&["validate", "-"], Some(xml.as_bytes()));
+    assert!(validated.status.success(), "{}", String::from_utf8_lossy(&validated.stdout));
+}
+
+#[test]
+fn output_is_written_to_a_file() {
+    let path = std::env::temp_dir().join("forestry_xml_parser_cli.geojson");
+    let path = path.to_string_lossy().to_string();
+
+    let result = run(&["to-json", "xml_history/XML_MV_K3421F.xml", "--geojson", "-o", &path], None);
+    assert!(result.status.success());
+    assert!(result.stdout.is_empty());
+
+    let geojson: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(geojson["type"], "FeatureCollection");
+}
+
+#[test]
+fn summary_lists_stands_and_operations() {
+    let result = run(&["summary", "orig_forestpropertydata.xml"], None);
+    let text = String::from_utf8(result.stdout).unwrap();
+
+    assert!(result.status.success());
+    assert!(text.contains("Stands: 176"), "{}", text);
+    assert!(text.contains("Operations: 187"), "{}", text);
+    assert!(text.contains("Growing stock, 546.88 ha"), "{}", text);
+    assert!(text.contains("Carbon: "), "{}", text);
+}
+
+#[test]
+fn summary_prints_volume_tables_per_parcel() {
+    let result = run(&["summary", "orig_forestpropertydata.xml", "--volumes-by", "parcel"], None);
+    let text = String::from_utf8(result.stdout).unwrap();
+
+    assert!(result.status.success());
+    assert_eq!(text.matches("Parcel ").count(), 5, "{}", text);
+    assert!(text.contains("Parcel 0 (350875), 138.19 ha"), "{}", text);
+    assert!(text.lines().any(|line| line.trim_start().starts_with("Scots pine")), "{}", text);
+}
+
+#[test]
+fn failures_exit_with_non_zero_status() {
+    let missing = run(&["to-json", "no_such_file.xml"], None);
+    assert!(!missing.status.success());
+    assert!(String::from_utf8_lossy(&missing.stderr).starts_with("Error: "));
+
+    let invalid = run(&["to-json", "-"], Some(b"<ForestPropertyData><Stands>"));
+    assert!(!invalid.status.success());
+
+    let areas = run(&["validate", "orig_forestpropertydata.xml", "--area-tolerance", "0.2"], None);
+    assert!(!areas.status.success());
+    assert!(String::from_utf8_lossy(&areas.stdout).contains("stand 2553958"));
+}
+
+#[test]
+fn batch_reports_and_fails_on_bad_files() {
+    let dir = std::env::temp_dir().join("forestry_xml_parser_cli_batch");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::copy("xml_history/XML_MV_K3244F.xml", dir.join("good.xml")).unwrap();
+    std::fs::write(dir.join("bad.xml"), "not xml").unwrap();
+    let output_dir = dir.join("out");
+
+    let result = run(&["batch", &dir.to_string_lossy(), "--output-dir", &output_dir.to_string_lossy(), "--jobs", "2"], None);
+    let text = String::from_utf8(result.stdout).unwrap();
+
+    assert!(!result.status.success());
+    assert!(text.starts_with("Converted 1 of 2 files, 1 failed"), "{}", text);
+    assert!(output_dir.join("good.json").exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn batch_xml_needs_an_output_directory() {
+    let dir = std::env::temp_dir().join("forestry_xml_parser_cli_batch_xml");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::copy("xml_history/XML_MV_K3244F.xml", dir.join("good.xml")).unwrap();
+    let original = std::fs::read(dir.join("good.xml")).unwrap();
+
+    let result = run(&["batch", &dir.to_string_lossy(), "--format", "xml"], None);
+    assert!(!result.status.success());
+    assert!(String::from_utf8(result.stderr).unwrap().contains("--output-dir"));
+
+    let result = run(&["batch", &dir.to_string_lossy(), "--format", "xml", "--output-dir", &dir.to_string_lossy()], None);
+    assert!(!result.status.success());
+    assert!(String::from_utf8(result.stderr).unwrap().contains("output directory other than the input directory"));
+    assert_eq!(std::fs::read(dir.join("good.xml")).unwrap(), original);
+
+    let output_dir = dir.join("out");
+    let result = run(&["batch", &dir.to_string_lossy(), "--format", "xml", "--output-dir", &output_dir.to_string_lossy()], None);
+    assert!(result.status.success());
+    assert!(output_dir.join("good.xml").exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn split_writes_a_document_per_parcel() {
+    let dir = std::env::temp_dir().join("forestry_xml_parser_cli_split");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let result = run(&["split", "orig_forestpropertydata.xml", "--by", "parcel", "--output-dir", &dir.to_string_lossy()], None);
+
+    assert!(result.status.success());
+    assert_eq!(String::from_utf8(result.stdout).unwrap().trim(), format!("Wrote 5 documents to {}", dir.display()));
+    assert!(dir.join("350875.xml").exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn split_does_not_overwrite_repeated_ids() {
+    let dir = std::env::temp_dir().join("forestry_xml_parser_cli_split_repeated");
+    let _ = std::fs::remove_dir_all(&dir);
+    // The document is ISO-8859-1, so the id is replaced in the bytes
+    let mut xml = std::fs::read("orig_forestpropertydata.xml").unwrap();
+    let position = xml.windows(9).position(|w| w == b"\"2553942\"").unwrap();
+    xml[position + 7] = b'1';
+
+    let result = run(&["split", "-", "--by", "stand", "--output-dir", &dir.to_string_lossy()], Some(&xml));
+
+    assert!(result.status.success());
+    assert!(String::from_utf8(result.stderr).unwrap().contains("id 2553941 occurs more than once, written to 2553941_2.xml"));
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 176);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn diff_exits_with_failure_when_documents_differ() {
+    let same = run(&["diff", "xml_history/XML_MV_K3421F.xml", "xml_history/XML_MV_K3421F.xml"], None);
+    assert!(same.status.success());
+
+    let different = run(&["diff", "xml_history/XML_MV_K3244F.xml", "xml_history/XML_MV_L3132A.xml", "--json"], None);
+    assert_eq!(different.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&different.stdout).unwrap();
+    assert_eq!(json["changes"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn validate_reports_summary_deviations() {
+    let result = run(&["validate", "xml_stands/XML_MV_L5121E.xml", "--summaries"], None);
+    let text = String::from_utf8(result.stdout).unwrap();
+
+    assert!(!result.status.success());
+    assert!(text.contains("Summary: stand 6787234 2024-07-31 (type 2): StemCount is 625 in the summary"), "{}", text);
+}
+
+#[test]
+fn harvest_groups_proposed_cuttings() {
+    let result = run(&["harvest", "xml_history/XML_MV_K3421F.xml"], None);
+    assert!(result.status.success());
+    let text = String::from_utf8(result.stdout).unwrap();
+    assert!(text.contains("2023 Thinning: 1 stands, 4.70 ha, 204.0 m³"), "{}", text);
+
+    let json = run(&["harvest", "xml_history/XML_MV_K3421F.xml", "--json"], None);
+    let json: serde_json::Value = serde_json::from_slice(&json.stdout).unwrap();
+    assert_eq!(json["groups"][0]["operation_type"], "3");
+}
+
+#[test]
+fn value_reads_a_price_table() {
+    let dir = std::env::temp_dir().join("forestry_xml_parser_value");
+    std::fs::create_dir_all(&dir).unwrap();
+    let prices = dir.join("prices.json");
+    std::fs::write(&prices, r#"{"prices": [{"stem_type": 1, "price": 50}, {"stem_type": 5, "price": 20}]}"#).unwrap();
+
+    let result = run(&["value", "xml_history/XML_MV_K3421F.xml", "--prices", prices.to_str().unwrap()], None);
+    assert!(result.status.success());
+    let text = String::from_utf8(result.stdout).unwrap();
+    assert!(text.contains("Area: 44.59 ha"), "{}", text);
+    assert!(text.contains("Proposed cuttings: "), "{}", text);
+    assert!(text.contains("  2023: "), "{}", text);
+}
@@ -0,0 +1,73 @@
+use forestry_xml_parser::codes::{ChangeState, FeatureCode, InvalidCode, OperationType, ProposalType, StemType, TreeSpecies, TreeStandDataType};
+use forestry_xml_parser::encoding::read_xml_file;
+use forestry_xml_parser::error::ForestDataError;
+use forestry_xml_parser::forest_property_data::ForestPropertyData;
+
+#[test]
+fn listed_codes_are_parsed() {
+    assert_eq!(TreeSpecies::try_from("1"), Ok(TreeSpecies::ScotsPine));
+    assert_eq!(TreeSpecies::try_from(" 29 "), Ok(TreeSpecies::Deciduous));
+    assert_eq!(OperationType::try_from("5"), Ok(OperationType::ClearCutting));
+    assert_eq!(OperationType::try_from("12"), Ok(OperationType::ReleaseCutting));
+    assert_eq!(OperationType::try_from("520"), Ok(OperationType::Planting));
+    assert_eq!(ChangeState::try_from("3"), Ok(ChangeState::Deleted));
+    assert_eq!(TreeStandDataType::try_from("3"), Ok(TreeStandDataType::Forecast));
+
+    assert_eq!(TreeSpecies::ScotsPine.code(), "1");
+    assert_eq!(TreeSpecies::ScotsPine.finnish_name(), Some("Mänty"));
+    assert_eq!(TreeSpecies::ScotsPine.english_name(), Some("Scots pine"));
+}
+
+#[test]
+fn unlisted_codes_of_open_lists_are_unknown() {
+    for code in ["302", "410", "660"] {
+        let operation_type = OperationType::try_from(code).unwrap();
+        assert_eq!(operation_type, OperationType::Unknown(code.to_string()));
+        assert_eq!(operation_type.code(), code);
+        assert_eq!(operation_type.english_name(), None);
+    }
+    assert_eq!(FeatureCode::try_from("1102"), Ok(FeatureCode::Unknown("1102".to_string())));
+    assert_eq!(StemType::try_from("11"), Ok(StemType::Unknown("11".to_string())));
+    assert_eq!(TreeSpecies::try_from(""), Ok(TreeSpecies::Unknown(String::new())));
+}
+
+#[test]
+fn malformed_and_unlisted_closed_codes_are_rejected() {
+    assert_eq!(
+        TreeSpecies::try_from("1.0"),
+        Err(InvalidCode { list: "TreeSpecies", code: "1.0".to_string() })
+    );
+    assert_eq!(
+        ChangeState::try_from("9"),
+        Err(InvalidCode { list: "ChangeState", code: "9".to_string() })
+    );
+    assert!(ProposalType::try_from("2").is_err());
+    assert!(TreeStandDataType::try_from("").is_err());
+    assert_eq!(ProposalType::try_from("2").unwrap_err().to_string(), "invalid ProposalType code `2`");
+}
+
+#[test]
+fn display_shows_both_names_or_the_code() {
+    assert_eq!(TreeSpecies::ScotsPine.to_string(), "Mänty / Scots pine");
+    assert_eq!(ChangeState::Updated.to_string(), "Muuttunut / Updated");
+    assert_eq!(OperationType::Unknown("660".to_string()).to_string(), "660");
+}
+
+#[test]
+fn codes_serialize_as_the_code() {
+    assert_eq!(serde_json::to_string(&OperationType::Unknown("660".to_string())).unwrap(), "\"660\"");
+    assert_eq!(serde_json::to_string(&ChangeState::Inserted).unwrap(), "\"1\"");
+    assert_eq!(serde_json::from_str::<TreeSpecies>("\"2\"").unwrap(), TreeSpecies::NorwaySpruce);
+    assert!(serde_json::from_str::<ChangeState>("\"4\"").is_err());
+}
+
+#[test]
+fn unlisted_closed_code_in_a_document_is_an_error() {
+    let xml = read_xml_file("xml_history/XML_MV_K3421F.xml").unwrap()
+        .replacen("<ts:TreeStandDataDate type=\"3\"", "<ts:TreeStandDataDate type=\"4\"", 1);
+    match ForestPropertyData::try_from_xml_str(&xml) {
+        Err(ForestDataError::Xml(e)) => assert!(e.message.contains("invalid TreeStandDataType code `4`"), "{}", e),
+        Err(e) => panic!("expected an XML error, got {}", e),
+        Ok(_) => panic!("expected an XML error"),
+    }
+}
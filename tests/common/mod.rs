@@ -0,0 +1,28 @@
+// Helpers shared by the integration tests; not every test uses all of them
+#![allow(dead_code)]
+
+use forestry_xml_parser::forest_property_data::{ForestPropertyData, StStand};
+
+pub const HISTORY: &str = "xml_history/XML_MV_K3421F.xml";
+
+pub fn load(path: &str) -> ForestPropertyData {
+    ForestPropertyData::try_from_xml_file(path).unwrap()
+}
+
+pub fn stand(path: &str, id: &str) -> StStand {
+    load(path).stands().find(|s| s.id == id).unwrap().clone()
+}
+
+// A stand of the history sample, e.g. 29727379 with measured, updated and
+// forecast tree stand data
+pub fn history_stand(id: &str) -> StStand {
+    stand(HISTORY, id)
+}
+
+pub fn approx(value: f64, expected: f64) -> bool {
+    (value - expected).abs() < 1e-9
+}
+
+pub fn approx_some(value: Option<f64>, expected: f64) -> bool {
+    value.is_some_and(|value| approx(value, expected))
+}
@@ -0,0 +1,98 @@
+use forestry_xml_parser::diff::{diff, ChangeKind, EntityKind, FieldChange};
+use forestry_xml_parser::encoding::read_xml_file;
+use forestry_xml_parser::forest_property_data::ForestPropertyData;
+
+const HISTORY: &str = "xml_history/XML_MV_K3421F.xml";
+
+fn load(xml: &str) -> ForestPropertyData {
+    ForestPropertyData::try_from_xml_str(xml).unwrap()
+}
+
+// A later version of the history file with stand 29727379 changed
+fn changed_version() -> ForestPropertyData {
+    let xml = read_xml_file(HISTORY).unwrap()
+        .replacen("<st:Area>5.20</st:Area>", "<st:Area>5.10</st:Area>", 1)
+        .replacen("<tst:Volume>1.3</tst:Volume>", "<tst:Volume>1.5</tst:Volume>", 1)
+        .replacen(
+            "        <st:SpecialFeature id=\"10864231\">\n          <sf:MainFeature>0</sf:MainFeature>\n          <sf:FeatureCode>103</sf:FeatureCode>\n        </st:SpecialFeature>\n",
+            "",
+            1,
+        );
+    load(&xml)
+}
+
+#[test]
+fn identical_documents_have_no_changes() {
+    let xml = read_xml_file(HISTORY).unwrap();
+    let changes = diff(&load(&xml), &load(&xml)).unwrap();
+    assert!(changes.is_empty());
+    assert_eq!(changes.to_string(), "0 added, 0 removed, 0 modified\n");
+}
+
+#[test]
+fn field_level_changes_are_reported() {
+    let before = load(&read_xml_file(HISTORY).unwrap());
+    let changes = diff(&before, &changed_version()).unwrap();
+    assert_eq!(changes.changes.len(), 3, "{}", changes);
+
+    let stand = &changes.changes[0];
+    assert_eq!((stand.kind, stand.id.as_str(), stand.change), (EntityKind::Stand, "29727379", ChangeKind::Modified));
+    assert_eq!(stand.fields, vec![FieldChange {
+        field: "StandBasicData/Area".to_string(),
+        before: Some("5.20".to_string()),
+        after: Some("5.10".to_string()),
+    }]);
+
+    let stratum = changes.changes.iter().find(|c| c.kind == EntityKind::Stratum).unwrap();
+    assert_eq!((stratum.id.as_str(), stratum.stand_id.as_str()), ("57687692", "29727379"));
+    assert_eq!(stratum.fields[0].field, "Volume");
+
+    let feature = changes.changes.iter().find(|c| c.kind == EntityKind::SpecialFeature).unwrap();
+    assert_eq!((feature.id.as_str(), feature.change), ("10864231", ChangeKind::Removed));
+    assert!(feature.fields.is_empty());
+
+    let text = changes.to_string();
+    assert!(text.starts_with("0 added, 1 removed, 2 modified\n"), "{}", text);
+    assert!(text.contains("modified stand 29727379\n  StandBasicData/Area: 5.20 -> 5.10\n"), "{}", text);
+    assert!(text.contains("removed special feature 10864231 in stand 29727379\n"), "{}", text);
+}
+
+#[test]
+fn reformatted_values_are_not_changes() {
+    let xml = read_xml_file(HISTORY).unwrap();
+    let reformatted = xml
+        .replacen("<st:Area>5.20</st:Area>", "<st:Area>5.2</st:Area>", 1)
+        .replacen("<tst:Volume>1.3</tst:Volume>", "<tst:Volume>1.30</tst:Volume>", 1)
+        .replacen("<tst:StemCount>311</tst:StemCount>", "<tst:StemCount> 311 </tst:StemCount>", 1);
+    assert_ne!(reformatted, xml);
+
+    let changes = diff(&load(&xml), &load(&reformatted)).unwrap();
+    assert!(changes.is_empty(), "{}", changes);
+}
+
+#[test]
+fn added_and_removed_stands() {
+    let before = load(&read_xml_file(HISTORY).unwrap());
+    let mut after = before.clone();
+    let stands = &mut after.st_stands.as_mut().unwrap().st_stand;
+    let mut added = stands.remove(0);
+    added.id = "1".to_string();
+    stands.push(added);
+
+    let changes = diff(&before, &after).unwrap();
+    let summary: Vec<_> = changes.changes.iter().map(|c| (c.kind, c.id.as_str(), c.change)).collect();
+    assert_eq!(summary, vec![
+        (EntityKind::Stand, "29727379", ChangeKind::Removed),
+        (EntityKind::Stand, "1", ChangeKind::Added),
+    ]);
+}
+
+#[test]
+fn changes_serialize_to_json() {
+    let before = load(&read_xml_file(HISTORY).unwrap());
+    let json = serde_json::to_value(diff(&before, &changed_version()).unwrap()).unwrap();
+    let first = &json["changes"][0];
+    assert_eq!(first["kind"], "stand");
+    assert_eq!(first["change"], "modified");
+    assert_eq!(first["fields"][0]["after"], "5.10");
+}
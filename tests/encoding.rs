@@ -0,0 +1,80 @@
+use std::fs;
+use encoding_rs::WINDOWS_1252;
+use forestry_xml_parser::encoding::{decode_xml, read_xml_file};
+use forestry_xml_parser::error::ForestDataError;
+use forestry_xml_parser::forest_property_data::ForestPropertyData;
+
+const ORIG: &str = "orig_forestpropertydata.xml";
+
+// The original document with the estate name written with an Ä, encoded as `label`
+fn estate_document(label: &str) -> Vec<u8> {
+    let xml = read_xml_file(ORIG).unwrap()
+        .replacen("encoding=\"iso-8859-1\"", &format!("encoding=\"{}\"", label), 1)
+        .replacen("ROVANIEMEN METS OPPILAITOS", "ROVANIEMEN METSÄOPPILAITOS", 1);
+    let (bytes, _, unmappable) = WINDOWS_1252.encode(&xml);
+    assert!(!unmappable);
+    bytes.into_owned()
+}
+
+fn estate_name(bytes: &[u8], file_name: &str) -> String {
+    let path = std::env::temp_dir().join(file_name);
+    fs::write(&path, bytes).unwrap();
+    let data = ForestPropertyData::try_from_xml_file(&path.to_string_lossy()).unwrap();
+    fs::remove_file(&path).unwrap();
+    let name = data.real_estates().next().unwrap().re_real_estate_name.clone();
+    name
+}
+
+#[test]
+fn iso_8859_1_is_decoded() {
+    let decoded = decode_xml(b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>\n<Name>ROVANIEMEN METS\xC4</Name>").unwrap();
+    assert!(decoded.ends_with("<Name>ROVANIEMEN METSÄ</Name>"), "{}", decoded);
+
+    let name = estate_name(&estate_document("iso-8859-1"), "forestry_xml_parser_latin1.xml");
+    assert_eq!(name, "ROVANIEMEN METSÄOPPILAITOS");
+}
+
+#[test]
+fn windows_1252_is_decoded() {
+    // 0x80 is the euro sign in Windows-1252 and a control character in ISO-8859-1
+    let decoded = decode_xml(b"<?xml version='1.0' encoding='windows-1252'?><Name>METS\xC4 \x80</Name>").unwrap();
+    assert!(decoded.ends_with("<Name>METSÄ €</Name>"), "{}", decoded);
+
+    let name = estate_name(&estate_document("windows-1252"), "forestry_xml_parser_cp1252.xml");
+    assert_eq!(name, "ROVANIEMEN METSÄOPPILAITOS");
+}
+
+#[test]
+fn repeated_byte_order_marks_are_removed() {
+    let mut bytes = b"\xEF\xBB\xBF\xEF\xBB\xBF".to_vec();
+    bytes.extend_from_slice("<?xml version=\"1.0\" encoding=\"utf-8\"?><Name>METSÄ</Name>".as_bytes());
+
+    let decoded = decode_xml(&bytes).unwrap();
+    assert!(decoded.starts_with("<?xml"), "{:?}", decoded);
+    assert!(decoded.ends_with("<Name>METSÄ</Name>"));
+
+    let xml = read_xml_file("xml_stands/XML_MV_L5121E.xml").unwrap();
+    let mut bytes = b"\xEF\xBB\xBF\xEF\xBB\xBF".to_vec();
+    bytes.extend_from_slice(xml.as_bytes());
+    let path = std::env::temp_dir().join("forestry_xml_parser_double_bom.xml");
+    fs::write(&path, &bytes).unwrap();
+    let data = ForestPropertyData::try_from_xml_file(&path.to_string_lossy());
+    fs::remove_file(&path).unwrap();
+    assert_eq!(data.unwrap().stands().count(), 8);
+}
+
+#[test]
+fn utf_16_is_detected_from_the_byte_order_mark() {
+    let mut utf16 = vec![0xFF, 0xFE];
+    utf16.extend("<?xml version=\"1.0\"?><Name>METSÄ</Name>".encode_utf16().flat_map(|unit| unit.to_le_bytes()));
+    assert_eq!(decode_xml(&utf16).unwrap(), "<?xml version=\"1.0\"?><Name>METSÄ</Name>");
+}
+
+#[test]
+fn invalid_bytes_are_an_encoding_error() {
+    let result = decode_xml(b"<?xml version=\"1.0\" encoding=\"utf-8\"?><Name>METS\xC4</Name>");
+    assert!(matches!(result, Err(ForestDataError::Encoding(_))));
+
+    let result = decode_xml(b"<?xml version=\"1.0\" encoding=\"no-such-encoding\"?><Name/>");
+    assert!(matches!(result, Err(ForestDataError::Encoding(message)) if message.contains("no-such-encoding")));
+}
@@ -0,0 +1,66 @@
+use forestry_xml_parser::encoding::read_xml_file;
+use forestry_xml_parser::error::{ForestDataError, XmlError};
+use forestry_xml_parser::forest_property_data::ForestPropertyData;
+
+const HISTORY: &str = "xml_history/XML_MV_K3421F.xml";
+
+fn xml_error(xml: &str) -> XmlError {
+    match ForestPropertyData::try_from_xml_str(xml) {
+        Err(ForestDataError::Xml(e)) => e,
+        Err(e) => panic!("expected an XML error, got {}", e),
+        Ok(_) => panic!("expected an XML error"),
+    }
+}
+
+// 1-based line and column of the first occurrence of `needle`
+fn location(xml: &str, needle: &str) -> (usize, usize) {
+    let position = xml.find(needle).unwrap();
+    let before = &xml[..position];
+    let line = before.matches('\n').count() + 1;
+    let column = before[before.rfind('\n').map_or(0, |p| p + 1)..].chars().count() + 1;
+    (line, column)
+}
+
+#[test]
+fn mistyped_value_is_located() {
+    let xml = read_xml_file(HISTORY).unwrap().replacen("<st:Area>5.20</st:Area>", "<st:Area>5,20</st:Area>", 1);
+    let error = xml_error(&xml);
+
+    assert_eq!(error.path, "Stands/Stand[id=29727379]/StandBasicData/Area");
+    assert_eq!((error.line, error.column), location(&xml, "<st:Area>5,20"));
+    assert_eq!((error.line, error.column), (32, 9));
+    assert!(error.message.contains("invalid decimal `5,20`"), "{}", error.message);
+    assert!(error.to_string().starts_with("XML error at Stands/Stand[id=29727379]/StandBasicData/Area (line 32, column 9)"), "{}", error);
+}
+
+#[test]
+fn missing_element_is_located() {
+    let xml = read_xml_file(HISTORY).unwrap()
+        .replacen("<st:StandNumber>", "<st:OtherNumber>", 1)
+        .replacen("</st:StandNumber>", "</st:OtherNumber>", 1);
+    let error = xml_error(&xml);
+
+    // Reported at the element that lacks the field
+    assert_eq!(error.path, "Stands/Stand[id=29727379]/StandBasicData");
+    assert_eq!((error.line, error.column), location(&xml, "<st:StandBasicData>"));
+    assert_eq!(error.message, "missing field `StandNumber`");
+}
+
+#[test]
+fn malformed_document_is_located() {
+    let xml = read_xml_file(HISTORY).unwrap().replacen("</st:StandBasicData>", "</st:StandBasicDataX>", 1);
+    let error = xml_error(&xml);
+
+    assert_eq!(error.path, "Stands/Stand/StandBasicData");
+    assert_eq!((error.line, error.column), location(&xml, "</st:StandBasicDataX>"));
+    assert!(error.message.contains("expected `</st:StandBasicData>`"), "{}", error.message);
+}
+
+#[test]
+fn nested_list_entries_are_located_by_id() {
+    let xml = read_xml_file(HISTORY).unwrap().replacen("<tst:Volume>1.3</tst:Volume>", "<tst:Volume>1.3 m3</tst:Volume>", 1);
+    let error = xml_error(&xml);
+
+    assert_eq!(error.path, "Stands/Stand[id=29727379]/TreeStandData/TreeStandDataDate[0]/TreeStrata/TreeStratum[id=57687692]/Volume");
+    assert_eq!((error.line, error.column), location(&xml, "<tst:Volume>1.3 m3"));
+}
@@ -0,0 +1,58 @@
+use forestry_xml_parser::forest_property_data::ForestPropertyData;
+use forestry_xml_parser::projection::Crs;
+
+#[test]
+fn feature_collection_has_one_feature_per_stand() {
+    for file in ["orig_forestpropertydata.xml", "forestpropertydata_url.xml", "xml_history/XML_MV_K3421F.xml"] {
+        let data = ForestPropertyData::try_from_xml_file(file).unwrap();
+        let geojson = data.to_geojson().unwrap();
+
+        assert_eq!(geojson["type"], "FeatureCollection");
+        assert_eq!(geojson["crs"]["properties"]["name"], "urn:ogc:def:crs:EPSG::3067");
+        assert_eq!(geojson["features"].as_array().unwrap().len(), data.stands().count(), "{}", file);
+    }
+}
+
+#[test]
+fn feature_carries_geometry_and_flattened_properties() {
+    let data = ForestPropertyData::try_from_xml_file("xml_history/XML_MV_K3421F.xml").unwrap();
+    let geojson = data.to_geojson().unwrap();
+    let feature = geojson["features"].as_array().unwrap().iter()
+        .find(|f| f["id"] == "29727379")
+        .unwrap();
+
+    assert_eq!(feature["geometry"]["type"], "Polygon");
+    let rings = feature["geometry"]["coordinates"].as_array().unwrap();
+    assert_eq!(rings.len(), 2);
+    assert_eq!(rings[0][0][0], 224825.6067);
+    assert_eq!(rings[0][0], rings[0][rings[0].as_array().unwrap().len() - 1]);
+
+    let properties = &feature["properties"];
+    assert_eq!(properties["stand_number"], "502");
+    assert_eq!(properties["area"], 5.2);
+    assert_eq!(properties["main_group"], "3");
+    assert_eq!(properties["fertility_class"], "7");
+    assert_eq!(properties["main_tree_species"], "1");
+    assert_eq!(properties["tree_stand_data_date"], "2030-01-01");
+    assert_eq!(properties["tree_stand_data_type"], "3");
+}
+
+#[test]
+fn summary_comes_from_the_latest_tree_stand_data() {
+    let data = ForestPropertyData::try_from_xml_file("orig_forestpropertydata.xml").unwrap();
+
+    for stand in data.stands() {
+        let feature = stand.to_geojson_feature(Crs::Tm35Fin).unwrap();
+        let Some(latest) = stand.latest_tree_stand_data() else {
+            assert!(feature["properties"]["volume"].is_null());
+            continue;
+        };
+        let dates = &stand.ts_tree_stand_data.as_ref().unwrap().ts_tree_stand_data_date;
+        assert!(dates.iter().all(|d| d.date.get() <= latest.date.get()));
+
+        match &latest.tss_tree_stand_summary {
+            Some(summary) => assert_eq!(feature["properties"]["volume"], summary.tss_volume.get()),
+            None => assert!(feature["properties"]["volume"].is_null()),
+        }
+    }
+}
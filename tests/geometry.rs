@@ -0,0 +1,80 @@
+use geo::Area;
+use forestry_xml_parser::forest_property_data::ForestPropertyData;
+use forestry_xml_parser::encoding::read_xml_file;
+use forestry_xml_parser::geometry::{parse_coordinates, AreaTolerance};
+
+#[test]
+fn stand_polygon_has_exterior_and_interior_rings() {
+    let data = ForestPropertyData::try_from_xml_file("xml_history/XML_MV_K3421F.xml").unwrap();
+    let stand = data.st_stands.as_ref().unwrap().st_stand.iter().find(|s| s.id == "29727379").unwrap();
+
+    let polygon = stand.polygon().unwrap();
+    assert_eq!(polygon.interiors().len(), 1);
+    assert!(polygon.exterior().is_closed());
+
+    // Area is given as 5.20 ha
+    let hectares = polygon.unsigned_area() / 10_000.0;
+    assert!((hectares - 5.20).abs() < 0.05, "{}", hectares);
+
+    let point = stand.centroid_point().unwrap();
+    assert_eq!((point.x(), point.y()), (224742.7381, 6652175.3160));
+}
+
+#[test]
+fn every_sample_stand_has_a_polygon() {
+    for file in ["forestpropertydata_url.xml", "xml_stands/XML_MV_L5121E.xml", "xml_history/XML_MV_K3244F.xml"] {
+        let data = ForestPropertyData::try_from_xml_file(file).unwrap();
+        let stands = data.st_stands.as_ref().unwrap();
+        assert_eq!(stands.multi_polygon().unwrap().0.len(), stands.st_stand.len());
+    }
+}
+
+#[test]
+fn estate_multi_polygon_covers_all_parcels() {
+    let data = ForestPropertyData::try_from_xml_file("orig_forestpropertydata.xml").unwrap();
+    let estate = data.real_estates().next().unwrap();
+
+    let stand_count: usize = estate.re_parcels.re_parcel.iter().map(|p| p.st_stands.st_stand.len()).sum();
+    assert!(stand_count > 0);
+    assert_eq!(estate.multi_polygon().unwrap().0.len(), stand_count);
+}
+
+#[test]
+fn invalid_coordinates_are_rejected() {
+    assert!(parse_coordinates("1.0,2.0 3.0").is_err());
+    assert!(parse_coordinates("1.0,x").is_err());
+    assert_eq!(parse_coordinates(" 1,2\n3,4 ").unwrap().len(), 2);
+}
+
+#[test]
+fn registered_and_computed_areas_agree() {
+    let data = ForestPropertyData::try_from_xml_file("tests/fixtures/square_stand.xml").unwrap();
+    let stand = data.stands().next().unwrap();
+
+    assert!((stand.computed_area_ha().unwrap() - 1.0).abs() < 1e-9);
+    assert_eq!(stand.check_area(AreaTolerance::Hectares(0.0)).unwrap(), None);
+}
+
+#[test]
+fn stands_outside_the_tolerance_are_flagged() {
+    let xml = read_xml_file("tests/fixtures/square_stand.xml").unwrap()
+        .replace("<st:Area>1.00</st:Area>", "<st:Area>1.20</st:Area><st:AreaDecrease>0.10</st:AreaDecrease>");
+    let data = ForestPropertyData::try_from_xml_str(&xml).unwrap();
+
+    assert!(data.check_areas(AreaTolerance::Hectares(0.2)).unwrap().is_empty());
+    assert!(data.check_areas(AreaTolerance::Relative(0.1)).unwrap().is_empty());
+
+    let mismatches = data.check_areas(AreaTolerance::Relative(0.05)).unwrap();
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].stand_id, "1001");
+    assert!((mismatches[0].registered_ha - 1.1).abs() < 1e-9);
+    assert!((mismatches[0].difference_ha() + 0.1).abs() < 1e-9);
+}
+
+#[test]
+fn digitized_sample_stands_match_their_registered_area() {
+    for file in ["forestpropertydata_url.xml", "xml_stands/XML_MV_V5112F.xml", "xml_history/XML_MV_K3421B.xml"] {
+        let data = ForestPropertyData::try_from_xml_file(file).unwrap();
+        assert!(data.check_areas(AreaTolerance::default()).unwrap().is_empty(), "{}", file);
+    }
+}
@@ -0,0 +1,75 @@
+use forestry_xml_parser::codes::{OperationType, StemType, TreeSpecies};
+use forestry_xml_parser::harvest::HarvestPlan;
+use forestry_xml_parser::values::Typed;
+
+mod common;
+
+use common::{approx, load, stand, HISTORY};
+
+#[test]
+fn percentages_are_resolved_against_cutting_volume() {
+    let stand = stand(HISTORY, "29727504");
+    let plan = stand.harvest_plan();
+    assert_eq!(plan.groups.len(), 1);
+
+    let group = &plan.groups[0];
+    assert_eq!(group.year, 2023);
+    assert_eq!(group.operation_type, OperationType::Thinning);
+    assert_eq!(group.stand_ids, ["29727504"]);
+    assert!(approx(group.area_ha, 4.7));
+    assert!(approx(group.volume, 43.4 * 4.7));
+
+    let first = &group.assortments[0];
+    assert_eq!((&first.species, &first.stem_type), (&TreeSpecies::ScotsPine, &StemType::PulpWood));
+    assert!(approx(first.volume, 43.4 * 0.135 * 4.7));
+    let last = group.assortments.last().unwrap();
+    assert_eq!(last.species.code(), "103");
+    assert!(approx(last.volume, 43.4 * 0.632 * 4.7));
+
+    let total: f64 = group.assortments.iter().map(|removal| removal.volume).sum();
+    assert!(approx(total, group.volume));
+}
+
+#[test]
+fn assortment_volume_takes_precedence_over_percent() {
+    let mut stand = stand("orig_forestpropertydata.xml", "2553941");
+    let operation = &mut stand.op_operations.as_mut().unwrap().op_operation[0];
+    let cutting = operation.op_cutting.as_mut().unwrap();
+    let assortment = &mut cutting.op_assortments.as_mut().unwrap().op_assortment[0];
+    assortment.op_assortment_percent = Some(Typed::parse("10").unwrap());
+
+    let volumes = cutting.assortment_volumes();
+    assert!(approx(volumes[0].volume, 97.5));
+    assert_eq!(volumes[0].stem_type, StemType::SawLog);
+
+    cutting.op_cutting_volume = None;
+    let sum: f64 = cutting.assortment_volumes().iter().map(|removal| removal.volume).sum();
+    assert!(approx(cutting.removal().unwrap(), sum));
+}
+
+#[test]
+fn completed_operations_are_left_out() {
+    let mut data = load("orig_forestpropertydata.xml");
+    let plan = data.harvest_plan();
+    let operations: usize = plan.groups.iter().map(|group| group.operation_count).sum();
+
+    for stand in data.stands_mut() {
+        for operation in stand.op_operations.iter_mut().flat_map(|operations| &mut operations.op_operation) {
+            operation.op_completion_data = None;
+        }
+    }
+    let all: usize = data.harvest_plan().groups.iter().map(|group| group.operation_count).sum();
+    assert_eq!(all, operations + 1);
+
+    let keys: Vec<_> = plan.groups.iter().map(|group| (group.year, group.operation_type.code().parse::<u32>().unwrap())).collect();
+    let mut sorted = keys.clone();
+    sorted.sort();
+    assert_eq!(keys, sorted);
+}
+
+#[test]
+fn empty_plan_has_no_negative_zero() {
+    let plan = HarvestPlan::default();
+    assert!(plan.volume().is_sign_positive());
+    assert_eq!(plan.to_string(), "Total: 0.0 m³");
+}
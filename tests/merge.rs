@@ -0,0 +1,134 @@
+use forestry_xml_parser::encoding::read_xml_file;
+use forestry_xml_parser::forest_property_data::{ForestPropertyData, OpOperation, StStand};
+use forestry_xml_parser::merge::{latest_change_time, ConflictPolicy, MergeConflict};
+use forestry_xml_parser::values::Typed;
+
+const ORIG: &str = "orig_forestpropertydata.xml";
+
+fn load(file: &str) -> ForestPropertyData {
+    ForestPropertyData::try_from_xml_file(file).unwrap()
+}
+
+// The original document with stand 2553941 renumbered and changed at `change_time`
+fn changed_copy(change_time: &str) -> ForestPropertyData {
+    let xml = read_xml_file(ORIG).unwrap().replacen(
+        "<co:ChangeTime>2018-08-16T15:58:13</co:ChangeTime>\n                <st:CompleteState>0</st:CompleteState>\n                <st:StandNumber>1109</st:StandNumber>",
+        &format!("<co:ChangeTime>{}</co:ChangeTime>\n                <st:CompleteState>0</st:CompleteState>\n                <st:StandNumber>999</st:StandNumber>", change_time),
+        1,
+    );
+    let data = ForestPropertyData::try_from_xml_str(&xml).unwrap();
+    assert!(data.stands().any(|s| s.st_stand_basic_data.st_stand_number == "999"));
+    data
+}
+
+fn stand_number(data: &ForestPropertyData, id: &str) -> String {
+    data.stands().find(|s| s.id == id).unwrap().st_stand_basic_data.st_stand_number.clone()
+}
+
+#[test]
+fn stand_files_are_combined() {
+    let mut merged = load("xml_stands/XML_MV_L5121E.xml");
+    let report = merged.merge([load("xml_stands/XML_MV_V4314F.xml"), load("xml_stands/XML_MV_V5112F.xml")], ConflictPolicy::Error).unwrap();
+
+    assert_eq!(report.added_stands, 70);
+    assert_eq!(merged.stands().count(), 78);
+    assert!(report.conflicts.is_empty());
+}
+
+#[test]
+fn identical_stands_are_deduplicated() {
+    let mut merged = load(ORIG);
+    let report = merged.merge([load(ORIG)], ConflictPolicy::Error).unwrap();
+
+    assert_eq!(report.added_stands, 0);
+    assert_eq!(report.duplicate_stands, 176);
+    assert_eq!(merged.stands().count(), 176);
+    assert_eq!(merged.real_estates().count(), 1);
+}
+
+#[test]
+fn newest_change_time_wins() {
+    let mut merged = load(ORIG);
+    let report = merged.merge([changed_copy("2019-01-01T00:00:00")], ConflictPolicy::NewestChangeTime).unwrap();
+    assert_eq!(report.conflicts, [MergeConflict { stand_id: "2553941".to_string(), replaced: true }]);
+    assert_eq!(stand_number(&merged, "2553941"), "999");
+
+    let mut merged = load(ORIG);
+    let report = merged.merge([changed_copy("2017-01-01T00:00:00")], ConflictPolicy::NewestChangeTime).unwrap();
+    assert!(!report.conflicts[0].replaced);
+    assert_eq!(stand_number(&merged, "2553941"), "1109");
+}
+
+#[test]
+fn first_wins_and_error_policies() {
+    let mut merged = load(ORIG);
+    let report = merged.merge([changed_copy("2019-01-01T00:00:00")], ConflictPolicy::FirstWins).unwrap();
+    assert!(!report.conflicts[0].replaced);
+    assert_eq!(stand_number(&merged, "2553941"), "1109");
+
+    let mut merged = load(ORIG);
+    let error = merged.merge([changed_copy("2019-01-01T00:00:00")], ConflictPolicy::Error).unwrap_err();
+    assert!(error.to_string().contains("2553941"), "{}", error);
+}
+
+#[test]
+fn failed_merge_leaves_the_document_unchanged() {
+    let mut merged = load(ORIG);
+    let others = [load("xml_stands/XML_MV_L5121E.xml"), changed_copy("2019-01-01T00:00:00")];
+    assert!(merged.merge(others, ConflictPolicy::Error).is_err());
+
+    assert_eq!(merged.stands().count(), 176);
+    assert!(merged.st_stands.is_none());
+    assert_eq!(stand_number(&merged, "2553941"), "1109");
+}
+
+#[test]
+fn values_are_compared_not_their_text() {
+    let xml = read_xml_file(ORIG).unwrap().replacen("<st:Area>5.9145</st:Area>", "<st:Area>5.91450</st:Area>", 1);
+    let mut merged = load(ORIG);
+    let report = merged.merge([ForestPropertyData::try_from_xml_str(&xml).unwrap()], ConflictPolicy::Error).unwrap();
+
+    assert_eq!(report.duplicate_stands, 176);
+    assert!(report.conflicts.is_empty());
+}
+
+#[test]
+fn estates_and_parcels_are_matched_by_id() {
+    let xml = read_xml_file(ORIG).unwrap();
+    let other_estate = ForestPropertyData::try_from_xml_str(&xml.replace("<re:RealEstate id=\"526637\">", "<re:RealEstate id=\"1\">")).unwrap();
+    let other_parcel = ForestPropertyData::try_from_xml_str(&xml.replace("<re:Parcel id=\"350875\">", "<re:Parcel id=\"2\">")).unwrap();
+
+    let mut merged = load(ORIG);
+    merged.merge([other_estate], ConflictPolicy::Error).unwrap();
+    assert_eq!(merged.real_estates().count(), 2);
+    assert_eq!(merged.stands().count(), 352);
+
+    let mut merged = load(ORIG);
+    let report = merged.merge([other_parcel], ConflictPolicy::Error).unwrap();
+    let estate = merged.real_estates().next().unwrap();
+    assert_eq!(merged.real_estates().count(), 1);
+    assert_eq!(estate.re_parcels.re_parcel.len(), 6);
+    assert_eq!(report.added_stands + report.duplicate_stands, 176);
+}
+
+#[test]
+fn change_times_inside_the_stand_are_compared() {
+    let data = load(ORIG);
+    let time = Typed::parse("2030-01-01T00:00:00").unwrap();
+    let newest = |stand: &StStand| latest_change_time(stand).unwrap().to_string();
+
+    let mut stand = data.stands().find(|s| s.ts_tree_stand_data.is_some()).unwrap().clone();
+    let date = &mut stand.ts_tree_stand_data.as_mut().unwrap().ts_tree_stand_data_date[0];
+    date.tst_tree_strata.as_mut().unwrap().tst_tree_stratum[0].co_change_time = Some(time.clone());
+    assert_eq!(newest(&stand), "2030-01-01 00:00:00");
+
+    let mut stand = data.stands().find(|s| s.st_special_features.is_some()).unwrap().clone();
+    stand.st_special_features.as_mut().unwrap().st_special_feature[0].co_change_time = Some(time.clone());
+    assert_eq!(newest(&stand), "2030-01-01 00:00:00");
+
+    let has_assortments = |o: &OpOperation| o.op_cutting.as_ref().is_some_and(|c| c.op_assortments.is_some());
+    let mut stand = data.stands().find(|s| s.op_operations.iter().flat_map(|o| &o.op_operation).any(has_assortments)).unwrap().clone();
+    let operation = stand.op_operations.as_mut().unwrap().op_operation.iter_mut().find(|o| has_assortments(o)).unwrap();
+    operation.op_cutting.as_mut().unwrap().op_assortments.as_mut().unwrap().op_assortment[0].co_change_time = Some(time);
+    assert_eq!(newest(&stand), "2030-01-01 00:00:00");
+}
@@ -0,0 +1,78 @@
+use forestry_xml_parser::encoding::read_xml_file;
+use forestry_xml_parser::error::{ForestDataError, XmlError};
+use forestry_xml_parser::forest_property_data::ForestPropertyData;
+
+const STANDS: &str = "xml_stands/XML_MV_L5121E.xml";
+const STAND_URI: &str = "http://standardit.tapio.fi/schemas/forestData/Stand";
+
+fn xml_error(xml: &str) -> XmlError {
+    match ForestPropertyData::try_from_xml_str(xml) {
+        Err(ForestDataError::Xml(e)) => e,
+        Err(e) => panic!("expected an XML error, got {}", e),
+        Ok(_) => panic!("expected an XML error"),
+    }
+}
+
+fn json(data: &ForestPropertyData) -> String {
+    serde_json::to_string(data).unwrap()
+}
+
+#[test]
+fn alternate_prefixes_are_resolved_by_uri() {
+    let original = read_xml_file(STANDS).unwrap();
+    let expected = json(&ForestPropertyData::try_from_xml_str(&original).unwrap());
+
+    let renamed = original
+        .replace("xmlns:st=", "xmlns:stand=")
+        .replace("<st:", "<stand:")
+        .replace("</st:", "</stand:");
+    let data = ForestPropertyData::try_from_xml_str(&renamed).unwrap();
+    assert_eq!(data.stands().count(), 8);
+    assert_eq!(json(&data), expected);
+
+    // The Stand namespace declared again as the default namespace of a stand
+    let default = original.replacen(
+        "<st:StandBasicData>",
+        &format!("<StandBasicData xmlns=\"{}\">", STAND_URI),
+        1,
+    ).replacen("</st:StandBasicData>", "</StandBasicData>", 1);
+    assert_eq!(json(&ForestPropertyData::try_from_xml_str(&default).unwrap()), expected);
+}
+
+#[test]
+fn element_in_the_wrong_namespace_is_rejected() {
+    let xml = read_xml_file(STANDS).unwrap().replacen("<st:Area>0.84</st:Area>", "<ts:Area>0.84</ts:Area>", 1);
+    let error = xml_error(&xml);
+    assert_eq!(error.path, "Stands/Stand/StandBasicData/Area");
+    assert_eq!(error.line, 30);
+    assert!(error.message.contains(&format!("is in namespace `http://standardit.tapio.fi/schemas/forestData/treeStand`, expected `{}`", STAND_URI)), "{}", error);
+}
+
+#[test]
+fn unknown_namespace_is_rejected() {
+    let xml = read_xml_file(STANDS).unwrap()
+        .replacen(&format!("xmlns:st=\"{}\"", STAND_URI), "xmlns:st=\"http://example.com/stand\"", 1);
+    let error = xml_error(&xml);
+    assert_eq!(error.path, "Stands");
+    assert!(error.message.contains("element `Stands` is in unknown namespace `http://example.com/stand`"), "{}", error);
+}
+
+#[test]
+fn unbound_elements_are_rejected() {
+    let xml = read_xml_file(STANDS).unwrap().replacen("<st:Area>0.84</st:Area>", "<xx:Area>0.84</xx:Area>", 1);
+    assert!(xml_error(&xml).message.contains("undeclared prefix `xx`"));
+
+    let xml = r#"<ForestPropertyData><Stands><Stand id="1"/></Stands></ForestPropertyData>"#;
+    assert!(xml_error(xml).message.contains("element `ForestPropertyData` is not in a namespace"));
+}
+
+#[test]
+fn documents_without_prefixes_are_read() {
+    let data = ForestPropertyData::try_from_xml_file("forestpropertydata_file_.xml").unwrap();
+    assert!(data.stands().count() > 0);
+
+    // A prefixed document may not leave single elements in the default namespace
+    let xml = read_xml_file(STANDS).unwrap().replacen("<st:Area>0.84</st:Area>", "<Area>0.84</Area>", 1);
+    let error = xml_error(&xml);
+    assert!(error.message.contains(&format!("is in namespace `http://standardit.tapio.fi/schemas/forestData`, expected `{}`", STAND_URI)), "{}", error);
+}
@@ -0,0 +1,80 @@
+use geo_types::Coord;
+use forestry_xml_parser::forest_property_data::ForestPropertyData;
+use forestry_xml_parser::projection::{tm35fin_to_wgs84, wgs84_to_tm35fin, Crs};
+
+// TM35FIN coordinates computed with Snyder's transverse Mercator formulas
+const REFERENCE: [((f64, f64), (f64, f64)); 5] = [
+    ((27.0, 60.0), (500000.0000, 6651411.1910)),
+    ((24.9384, 60.1699), (385611.3167, 6672118.3810)),
+    ((25.7, 66.5), (442158.2971, 7376245.0820)),
+    ((27.0, 69.9), (500000.0000, 7754721.4596)),
+    ((30.5, 64.0), (671127.0303, 7101713.9856)),
+];
+
+#[test]
+fn wgs84_to_tm35fin_matches_reference_points() {
+    for ((lon, lat), (x, y)) in REFERENCE {
+        let c = wgs84_to_tm35fin(Coord { x: lon, y: lat });
+        assert!((c.x - x).abs() < 0.01 && (c.y - y).abs() < 0.01, "({}, {}) -> {:?}", lon, lat, c);
+    }
+}
+
+#[test]
+fn tm35fin_to_wgs84_inverts_the_forward_projection() {
+    for ((lon, lat), (x, y)) in REFERENCE {
+        let c = tm35fin_to_wgs84(Coord { x, y });
+        assert!((c.x - lon).abs() < 1e-7 && (c.y - lat).abs() < 1e-7, "({}, {}) -> {:?}", x, y, c);
+    }
+
+    let original = Coord { x: 427874.679, y: 7372398.5855 };
+    let back = wgs84_to_tm35fin(tm35fin_to_wgs84(original));
+    assert!((back.x - original.x).abs() < 1e-6 && (back.y - original.y).abs() < 1e-6);
+}
+
+#[test]
+fn srs_name_variants_are_recognized() {
+    for name in ["EUREF-FIN", "EPSG:3067", "urn:ogc:def:crs:EPSG::3067", "http://www.opengis.net/gml/srs/epsg.xml#3067", " epsg:3067 "] {
+        assert_eq!(Crs::from_srs_name(name), Some(Crs::Tm35Fin), "{}", name);
+    }
+    for name in ["EPSG:4326", "urn:ogc:def:crs:OGC:1.3:CRS84", "WGS84"] {
+        assert_eq!(Crs::from_srs_name(name), Some(Crs::Wgs84), "{}", name);
+    }
+    assert_eq!(Crs::from_srs_name("EPSG:30670"), None);
+    assert_eq!(Crs::from_srs_name("KKJ"), None);
+}
+
+#[test]
+fn document_is_reprojected_and_back() {
+    let original = ForestPropertyData::try_from_xml_file("orig_forestpropertydata.xml").unwrap();
+    let mut data = ForestPropertyData::try_from_xml_file("orig_forestpropertydata.xml").unwrap();
+
+    data.reproject(Crs::Wgs84).unwrap();
+    for stand in data.stands() {
+        assert_eq!(stand.crs().unwrap(), Crs::Wgs84);
+        assert_eq!(stand.st_stand_basic_data.gdt_polygon_geometry.gml_point_property.gml_point.srs_name, "urn:ogc:def:crs:OGC:1.3:CRS84");
+        let point = stand.centroid_point().unwrap();
+        assert!((19.0..32.0).contains(&point.x()) && (59.0..71.0).contains(&point.y()), "{:?}", point);
+    }
+
+    // Area is still computed in TM35FIN
+    let before: f64 = original.stands().map(|s| s.computed_area_ha().unwrap()).sum();
+    let after: f64 = data.stands().map(|s| s.computed_area_ha().unwrap()).sum();
+    assert!((before - after).abs() < 0.01, "{} {}", before, after);
+
+    data.reproject(Crs::Tm35Fin).unwrap();
+    for (a, b) in original.stands().zip(data.stands()) {
+        let (p, q) = (a.centroid_point().unwrap(), b.centroid_point().unwrap());
+        assert!((p.x() - q.x()).abs() < 0.001 && (p.y() - q.y()).abs() < 0.001);
+        assert_eq!(b.st_stand_basic_data.gdt_polygon_geometry.gml_point_property.gml_point.srs_name, "EPSG:3067");
+    }
+}
+
+#[test]
+fn wgs84_geojson_has_no_crs_member() {
+    let data = ForestPropertyData::try_from_xml_file("xml_history/XML_MV_K3421F.xml").unwrap();
+    let geojson = data.to_geojson_in(Crs::Wgs84).unwrap();
+
+    assert!(geojson.get("crs").is_none());
+    let lon = geojson["features"][0]["geometry"]["coordinates"][0][0][0].as_f64().unwrap();
+    assert!((19.0..32.0).contains(&lon));
+}
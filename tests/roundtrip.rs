@@ -0,0 +1,172 @@
+use std::fs;
+use forestry_xml_parser::compare::{compare_xml, Difference};
+use forestry_xml_parser::encoding::read_xml_file;
+use forestry_xml_parser::forest_property_data::ForestPropertyData;
+use forestry_xml_parser::{PrefixStyle, XmlWriteOptions};
+
+fn sample_files() -> Vec<String> {
+    let mut files = vec![
+        "orig_forestpropertydata.xml".to_string(),
+        "forestpropertydata_url.xml".to_string(),
+        "tests/fixtures/square_stand.xml".to_string(),
+    ];
+
+    for dir in ["xml_stands", "xml_history"] {
+        let mut entries: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "xml"))
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        entries.sort();
+        files.extend(entries);
+    }
+
+    files
+}
+
+// XML -> ForestPropertyData -> JSON -> ForestPropertyData -> XML
+fn round_trip(file: &str, opts: &XmlWriteOptions) -> String {
+    let original = read_xml_file(file).unwrap();
+    let data = ForestPropertyData::try_from_xml_str(&original)
+        .unwrap_or_else(|e| panic!("{}: {}", file, e));
+
+    let json = serde_json::to_string(&data).unwrap();
+    let data: ForestPropertyData = serde_json::from_str(&json)
+        .unwrap_or_else(|e| panic!("{}: {}", file, e));
+
+    String::from_utf8(data.to_xml(opts).unwrap()).unwrap()
+}
+
+// The header comments of a document, without the comment this parser adds
+fn source_comments(xml: &str) -> Vec<String> {
+    let data = ForestPropertyData::try_from_xml_str(xml).unwrap();
+    data.header_comments.into_iter().filter(|c| !c.starts_with("Parsed with forestry_xml_parser")).collect()
+}
+
+fn assert_lossless(opts: &XmlWriteOptions) {
+    let mut report = Vec::new();
+
+    for file in sample_files() {
+        let original = read_xml_file(&file).unwrap();
+        let written = round_trip(&file, opts);
+        for difference in compare_xml(&original, &written).unwrap() {
+            report.push(format!("{}: {}", file, difference));
+        }
+
+        // compare_xml ignores comments
+        let (before, after) = (source_comments(&original), source_comments(&written));
+        if opts.header_comments && before != after {
+            report.push(format!("{}: header comments {:?} written as {:?}", file, before, after));
+        }
+    }
+
+    assert!(report.is_empty(), "round trip lost data:\n{}", report.join("\n"));
+}
+
+#[test]
+fn every_sample_file_is_found() {
+    let files = sample_files();
+    assert!(files.iter().any(|f| f.starts_with("xml_stands")));
+    assert!(files.iter().any(|f| f.starts_with("xml_history")));
+}
+
+#[test]
+fn header_comments_are_kept_in_json() {
+    let data = ForestPropertyData::try_from_xml_file("xml_stands/XML_MV_L5121E.xml").unwrap();
+    let json = serde_json::to_string(&data).unwrap();
+    assert!(json.contains(r#""_comments":["Generated at 2024/9/9 15:57:50. MTStd-version = MV19."]"#), "{}", &json[json.len() - 200..]);
+
+    let data: ForestPropertyData = serde_json::from_str(&json).unwrap();
+    let xml = String::from_utf8(data.to_xml(&XmlWriteOptions::default()).unwrap()).unwrap();
+    assert!(xml.contains("<!--Generated at 2024/9/9 15:57:50. MTStd-version = MV19.-->\n<!--Parsed with forestry_xml_parser"));
+    assert!(!xml.contains("_comments"));
+    assert!(!data.to_xml_string(&PrefixStyle::default()).unwrap().contains("_comments"));
+}
+
+#[test]
+fn real_estates_are_read_as_a_list() {
+    let xml = read_xml_file("orig_forestpropertydata.xml").unwrap();
+    let start = xml.find("<re:RealEstate id=").unwrap();
+    let end = xml.find("</re:RealEstate>").unwrap() + "</re:RealEstate>".len();
+    let second = xml[start..end].replacen("<re:RealEstate id=\"526637\">", "<re:RealEstate id=\"1\">", 1);
+    let xml = format!("{}{}{}", &xml[..end], second, &xml[end..]);
+    let data = ForestPropertyData::try_from_xml_str(&xml).unwrap();
+    let ids: Vec<_> = data.real_estates().map(|estate| estate.id.as_str()).collect();
+    assert_eq!(ids, ["526637", "1"]);
+
+    // JSON written before RealEstate became a list has a single object
+    let data = ForestPropertyData::try_from_xml_file("orig_forestpropertydata.xml").unwrap();
+    let mut json = serde_json::to_value(&data).unwrap();
+    let estate = json["RealEstates"]["RealEstate"][0].take();
+    json["RealEstates"]["RealEstate"] = estate;
+    let data: ForestPropertyData = serde_json::from_value(json).unwrap();
+    assert_eq!(data.real_estates().count(), 1);
+    assert_eq!(data.stands().count(), 176);
+}
+
+#[test]
+fn round_trip_with_metsakeskus_prefixes() {
+    assert_lossless(&XmlWriteOptions::default());
+}
+
+#[test]
+fn round_trip_without_prefixes() {
+    assert_lossless(&XmlWriteOptions { prefix_style: PrefixStyle::Bare, indent: None, ..XmlWriteOptions::default() });
+}
+
+#[test]
+fn round_trip_as_iso_8859_1() {
+    let opts = XmlWriteOptions { encoding: "iso-8859-1".to_string(), ..XmlWriteOptions::default() };
+    let file = "orig_forestpropertydata.xml";
+    let original = read_xml_file(file).unwrap();
+    let data = ForestPropertyData::try_from_xml_str(&original).unwrap();
+
+    let path = std::env::temp_dir().join("forestry_xml_parser_roundtrip_latin1.xml");
+    let path = path.to_string_lossy().to_string();
+    data.write_xml_file(&path, &opts).unwrap();
+    let written = read_xml_file(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(compare_xml(&original, &written).unwrap(), Vec::new());
+}
+
+#[test]
+fn comparison_ignores_prefixes_and_whitespace() {
+    let a = r#"<ForestPropertyData xmlns:st="s"><st:Stands>
+        <st:Stand id="1"> <st:Area> 1.5 </st:Area> </st:Stand>
+    </st:Stands></ForestPropertyData>"#;
+    let b = r#"<ForestPropertyData><Stands><Stand id="1"><Area>1.5</Area></Stand></Stands></ForestPropertyData>"#;
+
+    assert_eq!(compare_xml(a, b).unwrap(), Vec::new());
+}
+
+#[test]
+fn comparison_reports_dropped_elements_and_attributes() {
+    let a = r#"<Root><Stands><Stand id="1" extra="x"><Area>1.5</Area><Unknown>a</Unknown></Stand></Stands></Root>"#;
+    let b = r#"<Root><Stands><Stand id="1"><Area>2.0</Area></Stand></Stands></Root>"#;
+
+    let differences = compare_xml(a, b).unwrap();
+    assert!(differences.contains(&Difference::DroppedAttribute {
+        path: "Root/Stands/Stand[id=1]".to_string(),
+        name: "extra".to_string(),
+    }));
+    assert!(differences.contains(&Difference::DroppedElement { path: "Root/Stands/Stand[id=1]/Unknown".to_string() }));
+    assert!(differences.contains(&Difference::ChangedText {
+        path: "Root/Stands/Stand[id=1]/Area".to_string(),
+        before: "1.5".to_string(),
+        after: "2.0".to_string(),
+    }));
+}
+
+#[test]
+fn elements_missing_from_the_model_are_reported() {
+    let original = read_xml_file("xml_stands/XML_MV_L5121E.xml").unwrap()
+        .replacen("<st:StandBasicData>", "<st:StandBasicData><st:NotInModel>1</st:NotInModel>", 1);
+    let data = ForestPropertyData::try_from_xml_str(&original).unwrap();
+    let written = String::from_utf8(data.to_xml(&XmlWriteOptions::default()).unwrap()).unwrap();
+
+    let differences = compare_xml(&original, &written).unwrap();
+    assert_eq!(differences.len(), 1);
+    assert!(differences[0].to_string().ends_with("Stand[id=6787173]/StandBasicData/NotInModel"), "{}", differences[0]);
+}
@@ -0,0 +1,49 @@
+use std::env;
+use std::fs;
+use std::process::Command;
+use forestry_xml_parser::encoding::read_xml_file;
+use forestry_xml_parser::forest_property_data::ForestPropertyData;
+use forestry_xml_parser::XmlWriteOptions;
+
+// Validates written documents against the Metsäkeskus ForestData.xsd with
+// xmllint. The schema is not distributed with the crate: point
+// FORESTDATA_XSD at a local copy of ForestData.xsd, with the schemas it
+// imports next to it, to run these tests.
+fn schema() -> Option<String> {
+    let schema = env::var("FORESTDATA_XSD").ok();
+    if schema.is_none() {
+        eprintln!("FORESTDATA_XSD is not set, skipping schema validation");
+    }
+    schema
+}
+
+fn assert_valid(schema: &str, file: &str) {
+    let data = ForestPropertyData::try_from_xml_str(&read_xml_file(file).unwrap()).unwrap();
+    let name = file.replace(['/', '.'], "_");
+    let path = env::temp_dir().join(format!("forestry_xml_parser_schema_{}.xml", name));
+    data.write_xml_file(&path.to_string_lossy(), &XmlWriteOptions::default()).unwrap();
+
+    let output = Command::new("xmllint")
+        .args(["--noout", "--schema", schema])
+        .arg(&path)
+        .output()
+        .expect("xmllint is required when FORESTDATA_XSD is set");
+    fs::remove_file(&path).unwrap();
+
+    assert!(output.status.success(), "{} is not valid:\n{}", file, String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn written_documents_are_valid() {
+    let Some(schema) = schema() else { return };
+    for file in ["xml_stands/XML_MV_L5121E.xml", "xml_history/XML_MV_K3421F.xml", "orig_forestpropertydata.xml"] {
+        assert_valid(&schema, file);
+    }
+}
+
+// The fixture is hand-written, see the comment at its top
+#[test]
+fn square_stand_fixture_is_valid() {
+    let Some(schema) = schema() else { return };
+    assert_valid(&schema, "tests/fixtures/square_stand.xml");
+}
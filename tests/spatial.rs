@@ -0,0 +1,108 @@
+use forestry_xml_parser::forest_property_data::ForestPropertyData;
+use forestry_xml_parser::projection::Crs;
+use forestry_xml_parser::spatial::{SpatialPredicate, SpatialQuery};
+
+const FIXTURE: &str = "tests/fixtures/square_stand.xml";
+// The western half of the fixture stand
+const WEST_HALF: &str = "POLYGON((223900 6651900, 224050 6651900, 224050 6652200, 223900 6652200, 223900 6651900))";
+
+fn stand_count(data: &ForestPropertyData) -> usize {
+    data.stands().count()
+}
+
+#[test]
+fn intersecting_and_contained_stands_are_selected() {
+    let query = SpatialQuery::from_wkt(WEST_HALF).unwrap();
+    let mut data = ForestPropertyData::try_from_xml_file(FIXTURE).unwrap();
+    data.filter_stands(&query).unwrap();
+    assert_eq!(stand_count(&data), 1);
+
+    let query = SpatialQuery { predicate: SpatialPredicate::Within, ..query };
+    let mut data = ForestPropertyData::try_from_xml_file(FIXTURE).unwrap();
+    data.filter_stands(&query).unwrap();
+    assert!(data.st_stands.is_none());
+}
+
+#[test]
+fn clipping_cuts_the_polygon_and_scales_the_area() {
+    let query = SpatialQuery { clip: true, ..SpatialQuery::from_wkt(WEST_HALF).unwrap() };
+    let mut data = ForestPropertyData::try_from_xml_file(FIXTURE).unwrap();
+    data.filter_stands(&query).unwrap();
+
+    let stand = data.stands().next().unwrap();
+    assert!((stand.computed_area_ha().unwrap() - 0.5).abs() < 1e-9);
+    assert_eq!(stand.st_stand_basic_data.st_area.text(), "0.50");
+
+    // The point at the centre of the original square is on the new boundary
+    let point = stand.centroid_point().unwrap();
+    assert!(point.x() <= 224050.0);
+}
+
+#[test]
+fn within_is_a_subset_of_intersects_on_sample_data() {
+    let file = "xml_stands/XML_MV_V5112F.xml";
+    let data = ForestPropertyData::try_from_xml_file(file).unwrap();
+    let first = data.stands().next().unwrap().centroid_point().unwrap();
+    let (x, y) = (first.x(), first.y());
+    let wkt = format!(
+        "POLYGON(({} {}, {} {}, {} {}, {} {}, {} {}))",
+        x - 500.0, y - 500.0, x + 500.0, y - 500.0, x + 500.0, y + 500.0, x - 500.0, y + 500.0, x - 500.0, y - 500.0
+    );
+
+    let count = |query: &SpatialQuery| {
+        let mut data = ForestPropertyData::try_from_xml_file(file).unwrap();
+        data.filter_stands(query).unwrap();
+        data
+    };
+
+    let intersects = count(&SpatialQuery::from_wkt(&wkt).unwrap());
+    let within = count(&SpatialQuery { predicate: SpatialPredicate::Within, ..SpatialQuery::from_wkt(&wkt).unwrap() });
+    let clipped = count(&SpatialQuery { clip: true, ..SpatialQuery::from_wkt(&wkt).unwrap() });
+
+    assert!(stand_count(&within) <= stand_count(&intersects));
+    assert!(stand_count(&intersects) > 0 && stand_count(&intersects) < stand_count(&data));
+    assert_eq!(stand_count(&clipped), stand_count(&intersects));
+
+    for stand in clipped.stands() {
+        let polygon = stand.polygon().unwrap();
+        assert!(polygon.exterior().0.iter().all(|c| (c.x - x).abs() <= 500.001 && (c.y - y).abs() <= 500.001));
+    }
+}
+
+#[test]
+fn stands_on_parcels_are_filtered_and_empty_parcels_removed() {
+    let mut data = ForestPropertyData::try_from_xml_file("orig_forestpropertydata.xml").unwrap();
+    let stand = data.stands().nth(3).unwrap();
+    let point = stand.centroid_point().unwrap();
+    let id = stand.id.clone();
+
+    let query = SpatialQuery::new(geo_types::Geometry::Point(point));
+    data.filter_stands(&query).unwrap();
+
+    let parcels = &data.real_estates().next().unwrap().re_parcels.re_parcel;
+    assert!(parcels.iter().all(|p| !p.st_stands.st_stand.is_empty()));
+    assert!(data.stands().any(|s| s.id == id));
+}
+
+#[test]
+fn wgs84_boundary_selects_the_same_stands() {
+    let wgs84 = "POLYGON((21.0 59.9, 21.05 59.9, 21.05 60.0, 21.0 60.0, 21.0 59.9))";
+    let query = SpatialQuery { crs: Crs::Wgs84, ..SpatialQuery::from_wkt(wgs84).unwrap() };
+    let mut data = ForestPropertyData::try_from_xml_file(FIXTURE).unwrap();
+    data.filter_stands(&query).unwrap();
+    assert_eq!(stand_count(&data), 0);
+
+    assert!(SpatialQuery::from_wkt("POLYGON((1 2, 3))").is_err());
+}
+
+#[test]
+fn failed_filter_leaves_the_document_unchanged() {
+    let mut data = ForestPropertyData::try_from_xml_file("orig_forestpropertydata.xml").unwrap();
+    let stand = data.stands_mut().last().unwrap();
+    let exterior = stand.st_stand_basic_data.gdt_polygon_geometry.gml_polygon_property.gml_polygon.gml_exterior.as_mut().unwrap();
+    exterior.gml_linear_ring.gml_coordinates = "not coordinates".to_string();
+
+    let query = SpatialQuery { clip: true, ..SpatialQuery::from_wkt(WEST_HALF).unwrap() };
+    assert!(data.filter_stands(&query).is_err());
+    assert_eq!(stand_count(&data), 176);
+}
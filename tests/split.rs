@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+use forestry_xml_parser::encoding::read_xml_file;
+use forestry_xml_parser::forest_property_data::ForestPropertyData;
+use forestry_xml_parser::merge::ConflictPolicy;
+use forestry_xml_parser::split::{file_names, SplitLevel};
+use forestry_xml_parser::XmlWriteOptions;
+
+const ORIG: &str = "orig_forestpropertydata.xml";
+
+fn load(file: &str) -> ForestPropertyData {
+    ForestPropertyData::try_from_xml_file(file).unwrap()
+}
+
+#[test]
+fn split_by_real_estate_and_parcel() {
+    let estates = load(ORIG).split(SplitLevel::RealEstate);
+    assert_eq!(estates.len(), 1);
+    assert_eq!(estates[0].id, "526637");
+    assert_eq!(estates[0].data.stands().count(), 176);
+
+    let parcels = load(ORIG).split(SplitLevel::Parcel);
+    assert_eq!(parcels.len(), 5);
+    assert_eq!(parcels.iter().map(|part| part.data.stands().count()).sum::<usize>(), 176);
+    for part in &parcels {
+        let estates = &part.data.re_real_estates.as_ref().unwrap().re_real_estate;
+        assert_eq!(estates.len(), 1);
+        assert_eq!(estates[0].id, "526637");
+        assert_eq!(estates[0].re_parcels.re_parcel[0].id, part.id);
+    }
+}
+
+#[test]
+fn split_by_stand_keeps_root_and_parents() {
+    let original = load(ORIG);
+    let parts = original.clone().split(SplitLevel::Stand);
+    assert_eq!(parts.len(), 176);
+
+    let part = parts.iter().find(|part| part.id == "2553941").unwrap();
+    let stands: Vec<_> = part.data.stands().collect();
+    assert_eq!(stands.len(), 1);
+    assert_eq!(stands[0].st_stand_basic_data.st_stand_number, "1109");
+    assert_eq!(part.data.real_estates().next().unwrap().id, "526637");
+    assert_eq!(part.data.header_comments, original.header_comments);
+
+    let xml = part.data.to_xml(&XmlWriteOptions::default()).unwrap();
+    let xml = String::from_utf8(xml).unwrap();
+    assert!(xml.contains("xmlns:st="));
+    assert!(xml.contains("xsi:schemaLocation="));
+}
+
+#[test]
+fn root_stands_are_split_and_merged_back() {
+    let parts = load("forestpropertydata_url.xml").split(SplitLevel::Stand);
+    assert_eq!(parts.len(), 38);
+
+    let mut parsed = parts.iter().map(|part| {
+        let xml = part.data.to_xml(&XmlWriteOptions::default()).unwrap();
+        ForestPropertyData::try_from_xml_str(&String::from_utf8(xml).unwrap()).unwrap()
+    });
+    let mut merged = parsed.next().unwrap();
+    let report = merged.merge(parsed, ConflictPolicy::Error).unwrap();
+    assert_eq!(report.added_stands, 37);
+    assert_eq!(merged.stands().count(), 38);
+
+    let grouped = load("forestpropertydata_url.xml").split(SplitLevel::RealEstate);
+    assert_eq!(grouped.len(), 1);
+    assert_eq!(grouped[0].id, "");
+}
+
+#[test]
+fn repeated_ids_get_distinct_file_names() {
+    let xml = read_xml_file(ORIG).unwrap().replacen("<st:Stand id=\"2553942\">", "<st:Stand id=\"2553941\">", 1);
+    let parts = ForestPropertyData::try_from_xml_str(&xml).unwrap().split(SplitLevel::Stand);
+    let names = file_names(&parts);
+
+    assert_eq!(names.iter().collect::<HashSet<_>>().len(), 176);
+    assert_eq!(names[0], "2553941.xml");
+    assert_eq!(names[1], "2553941_2.xml");
+    assert_eq!(names[2], "2553943.xml");
+
+    let parts = load("forestpropertydata_url.xml").split(SplitLevel::Parcel);
+    assert_eq!(file_names(&parts), ["stands.xml"]);
+}
@@ -0,0 +1,57 @@
+use forestry_xml_parser::codes::TreeStandDataType;
+use forestry_xml_parser::forest_property_data::ForestPropertyData;
+use forestry_xml_parser::stand_summary::SummaryTolerance;
+use forestry_xml_parser::values::Typed;
+
+mod common;
+
+use common::{approx_some, history_stand};
+
+#[test]
+fn summary_is_derived_from_strata() {
+    let stand = history_stand("29727379");
+    let dates = &stand.ts_tree_stand_data.as_ref().unwrap().ts_tree_stand_data_date;
+    let derived = dates[1].derived_summary().unwrap();
+
+    assert!(approx_some(derived.basal_area, 0.5));
+    assert_eq!(derived.stem_count, Some(1213));
+    assert!(approx_some(derived.volume, 1.3));
+    assert!(approx_some(derived.volume_growth, 0.17));
+    assert!(approx_some(derived.mean_age, (0.2 * 10.0 + 0.1 * 11.0 + 0.2 * 8.0) / 0.5));
+    assert!(approx_some(derived.mean_diameter, (0.2 * 2.9 + 0.1 * 2.1 + 0.2 * 2.3) / 0.5));
+    assert!(approx_some(derived.mean_height, (0.2 * 3.2 + 0.1 * 3.7 + 0.2 * 3.5) / 0.5));
+    assert!(approx_some(derived.stump_biomass, 0.06 + 0.29 + 0.32));
+
+    // The forecast has a summary but no strata
+    assert_eq!(dates[2].derived_summary(), None);
+}
+
+#[test]
+fn consistent_summaries_pass() {
+    let data = ForestPropertyData::try_from_xml_file("xml_history/XML_MV_K3421F.xml").unwrap();
+    assert!(data.check_summaries(SummaryTolerance::default()).is_empty());
+}
+
+#[test]
+fn deviating_fields_are_listed() {
+    let mut stand = history_stand("29727379");
+    let date = &mut stand.ts_tree_stand_data.as_mut().unwrap().ts_tree_stand_data_date[1];
+    let summary = date.tss_tree_stand_summary.as_mut().unwrap();
+    summary.tss_volume = Typed::parse("4.0").unwrap();
+    summary.tss_mean_age = Typed::parse("20").unwrap();
+
+    let deviations = stand.check_summaries(SummaryTolerance::default());
+    let fields: Vec<_> = deviations.iter().map(|d| d.field).collect();
+    assert_eq!(fields, ["Volume", "MeanAge"]);
+    assert_eq!(deviations[0].data_type, TreeStandDataType::Updated);
+    assert_eq!(deviations[0].summary, 4.0);
+    assert!((deviations[0].derived - 1.3).abs() < 1e-9);
+    assert_eq!(
+        deviations[0].to_string(),
+        "stand 29727379 2020-01-01 (type 2): Volume is 4 in the summary, 1.30 from the strata"
+    );
+
+    // Without tolerance the rounding of the export shows up as well
+    let strict = stand.check_summaries(SummaryTolerance { absolute: 0.0, relative: 0.0 });
+    assert!(strict.len() > deviations.len());
+}
@@ -0,0 +1,62 @@
+use forestry_xml_parser::codes::TreeStandDataType;
+use forestry_xml_parser::timeseries::DataPointSource;
+
+mod common;
+
+use common::{approx_some, history_stand};
+
+#[test]
+fn points_are_ordered_by_date() {
+    let series = history_stand("29727379").time_series(&[]);
+
+    let dates: Vec<_> = series.iter().map(|point| point.date.to_string()).collect();
+    assert_eq!(dates, ["2017-06-22", "2020-01-01", "2030-01-01"]);
+    let types: Vec<_> = series.iter().map(|point| point.data_type.clone()).collect();
+    assert_eq!(types, [TreeStandDataType::Measured, TreeStandDataType::Updated, TreeStandDataType::Forecast]);
+}
+
+#[test]
+fn summaries_are_used_when_present() {
+    let series = history_stand("29727379").time_series(&[TreeStandDataType::Updated, TreeStandDataType::Forecast]);
+    assert_eq!(series.len(), 2);
+
+    let updated = &series[0];
+    assert_eq!(updated.source, DataPointSource::Summary);
+    assert!(approx_some(updated.volume, 1.4));
+    assert!(approx_some(updated.basal_area, 0.5));
+    assert!(approx_some(updated.mean_height, 3.4));
+    assert_eq!(updated.stem_count, Some(1213));
+    assert!(approx_some(updated.biomass.total(), 0.11 + 0.32 + 0.58 + 0.68));
+
+    let forecast = &series[1];
+    assert!(approx_some(forecast.volume, 4.0));
+    assert_eq!(forecast.biomass.total(), None);
+}
+
+#[test]
+fn strata_are_aggregated_without_a_summary() {
+    let series = history_stand("29727379").time_series(&[TreeStandDataType::Measured]);
+    assert_eq!(series.len(), 1);
+
+    let measured = &series[0];
+    assert_eq!(measured.source, DataPointSource::Strata);
+    assert!(approx_some(measured.volume, 1.3 + 0.6 + 0.3));
+    assert_eq!(measured.basal_area, None);
+    assert_eq!(measured.stem_count, Some(311 + 413 + 512));
+    // Weighted by stem count because the strata have no basal area
+    assert!(approx_some(measured.mean_height, (311.0 * 2.8 + 413.0 * 2.8 + 512.0 * 3.0) / 1236.0));
+}
+
+#[test]
+fn strata_mean_height_is_weighted_by_basal_area() {
+    let mut stand = history_stand("29727379");
+    let dates = &mut stand.ts_tree_stand_data.as_mut().unwrap().ts_tree_stand_data_date;
+    dates[1].tss_tree_stand_summary = None;
+
+    let series = stand.time_series(&[TreeStandDataType::Updated]);
+    let updated = &series[0];
+    assert_eq!(updated.source, DataPointSource::Strata);
+    assert!(approx_some(updated.basal_area, 0.2 + 0.1 + 0.2));
+    assert!(approx_some(updated.mean_height, (0.2 * 3.2 + 0.1 * 3.7 + 0.2 * 3.5) / 0.5));
+    assert!(approx_some(updated.biomass.stump, 0.06 + 0.29 + 0.32));
+}
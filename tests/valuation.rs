@@ -0,0 +1,98 @@
+use forestry_xml_parser::codes::{StemType, TreeSpecies};
+use forestry_xml_parser::forest_property_data::ForestPropertyData;
+use forestry_xml_parser::valuation::{PriceTable, PropertyValue};
+
+mod common;
+
+use common::{approx, history_stand};
+
+const PRICES: &str = r#"
+[[prices]]
+species = 1
+stem_type = 1
+price = 60.0
+
+[[prices]]
+stem_type = 1
+price = 50.0
+
+[[prices]]
+stem_type = "5"
+price = 20.0
+
+[[regions]]
+name = "Lappi"
+municipalities = [698]
+prices = [{ stem_type = 5, price = 12.0 }]
+"#;
+
+#[test]
+fn toml_and_json_tables_are_equal() {
+    let toml = PriceTable::from_toml_str(PRICES).unwrap();
+    let json = PriceTable::from_json_str(&serde_json::to_string(&toml).unwrap()).unwrap();
+    assert_eq!(toml, json);
+    assert_eq!(toml.prices[0].species, Some(TreeSpecies::ScotsPine));
+    assert_eq!(toml.regions[0].municipalities, ["698"]);
+
+    let error = PriceTable::from_toml_str("[[prices]]\nstem_type = \"5!\"\nprice = 1.0").unwrap_err();
+    assert!(error.to_string().starts_with("Price table error:"), "{}", error);
+}
+
+#[test]
+fn species_and_regional_prices_take_precedence() {
+    let prices = PriceTable::from_toml_str(PRICES).unwrap();
+    assert_eq!(prices.price(&TreeSpecies::ScotsPine, &StemType::SawLog, None), Some(60.0));
+    assert_eq!(prices.price(&TreeSpecies::NorwaySpruce, &StemType::SawLog, None), Some(50.0));
+    assert_eq!(prices.price(&TreeSpecies::NorwaySpruce, &StemType::PulpWood, None), Some(20.0));
+    assert_eq!(prices.price(&TreeSpecies::NorwaySpruce, &StemType::PulpWood, Some("0698")), Some(12.0));
+    assert_eq!(prices.price(&TreeSpecies::NorwaySpruce, &StemType::SawLog, Some("698")), Some(50.0));
+    assert_eq!(prices.price(&TreeSpecies::ScotsPine, &StemType::Unknown("8".to_string()), None), None);
+}
+
+#[test]
+fn stand_values_use_strata_and_proposed_cuttings() {
+    let prices = PriceTable::from_toml_str(PRICES).unwrap();
+    let stand = history_stand("29727504");
+    let value = stand.value(&prices, None);
+    assert_eq!(value.region, None);
+
+    let breakdown = stand.volume_breakdown();
+    let standing: f64 = breakdown.species.iter()
+        .map(|s| s.saw_log_volume * if s.species == TreeSpecies::ScotsPine { 60.0 } else { 50.0 } + s.pulp_wood_volume * 20.0)
+        .sum();
+    assert!(approx(value.standing_value, standing));
+
+    let plan = stand.harvest_plan();
+    let cutting: f64 = plan.groups[0].assortments.iter()
+        .map(|r| r.volume * if r.stem_type == StemType::SawLog { 50.0 } else { 20.0 })
+        .sum();
+    assert_eq!(value.cuttings.len(), 1);
+    assert_eq!(value.cuttings[0].year, 2023);
+    assert!(approx(value.cutting_value(), cutting));
+    assert!(approx(value.unpriced_volume, 0.0));
+}
+
+#[test]
+fn property_value_applies_regions_and_reports_unpriced_volume() {
+    let data = ForestPropertyData::try_from_xml_file("orig_forestpropertydata.xml").unwrap();
+    let prices = PriceTable::from_toml_str(PRICES).unwrap();
+    let value = data.value(&prices);
+    assert_eq!(value.stands.len(), 176);
+    assert!(value.stands.iter().all(|stand| stand.region.as_deref() == Some("Lappi")));
+    assert!(value.standing_value() > 0.0 && value.cutting_value() > 0.0);
+
+    let by_year = value.cutting_value_by_year();
+    assert!(by_year.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert!(approx(by_year.iter().map(|(_, value)| value).sum::<f64>(), value.cutting_value()));
+
+    let unpriced = data.value(&PriceTable::default());
+    assert!(approx(unpriced.standing_value(), 0.0));
+    assert!(unpriced.unpriced_volume() > 0.0);
+}
+
+#[test]
+fn empty_property_has_no_negative_zero() {
+    let value = PropertyValue::default();
+    let totals = [value.area_ha(), value.standing_value(), value.cutting_value(), value.unpriced_volume()];
+    assert!(totals.iter().all(|total| *total == 0.0 && total.is_sign_positive()));
+}
@@ -0,0 +1,78 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use forestry_xml_parser::encoding::read_xml_file;
+use forestry_xml_parser::forest_property_data::ForestPropertyData;
+use forestry_xml_parser::values::Typed;
+use forestry_xml_parser::XmlWriteOptions;
+
+const HISTORY: &str = "xml_history/XML_MV_K3421F.xml";
+
+#[test]
+fn original_text_is_kept() {
+    let area: Typed<f64> = Typed::parse("5.20").unwrap();
+    assert_eq!(area.get(), 5.2);
+    assert_eq!(area.text(), "5.20");
+    assert_eq!(area.to_string(), "5.20");
+
+    let count: Typed<u32> = Typed::parse("0311").unwrap();
+    assert_eq!(count.get(), 311);
+    assert_eq!(count.text(), "0311");
+
+    // Whitespace is ignored when parsing but kept in the text
+    let volume: Typed<f64> = Typed::parse(" 1.3\n").unwrap();
+    assert_eq!(volume.get(), 1.3);
+    assert_eq!(volume.text(), " 1.3\n");
+}
+
+#[test]
+fn dates_are_typed() {
+    let date: Typed<NaiveDate> = Typed::parse("2017-06-22").unwrap();
+    assert_eq!(date.get(), NaiveDate::from_ymd_opt(2017, 6, 22).unwrap());
+
+    let time: Typed<NaiveDateTime> = Typed::parse("2018-08-16T15:54:47.120").unwrap();
+    assert_eq!(time.get().format("%H:%M:%S%.3f").to_string(), "15:54:47.120");
+    assert_eq!(time.text(), "2018-08-16T15:54:47.120");
+
+    assert!(Typed::<NaiveDate>::parse("22.6.2017").is_err());
+}
+
+#[test]
+fn invalid_text_is_rejected() {
+    let error = Typed::<f64>::parse("5,20").unwrap_err();
+    assert!(error.starts_with("invalid decimal `5,20`"), "{}", error);
+    assert!(Typed::<u32>::parse("-1").is_err());
+    assert!(Typed::<u32>::parse("").is_err());
+}
+
+#[test]
+fn new_and_set_write_the_canonical_text() {
+    let mut area = Typed::new(5.0);
+    assert_eq!(area.text(), "5");
+
+    area.set(5.25);
+    assert_eq!(area.get(), 5.25);
+    assert_eq!(area.text(), "5.25");
+
+    // Equality compares the values, not the text
+    assert_eq!(Typed::<f64>::parse("5.20").unwrap(), Typed::parse("5.2").unwrap());
+}
+
+#[test]
+fn text_survives_json_and_xml() {
+    let area: Typed<f64> = Typed::parse("5.20").unwrap();
+    let json = serde_json::to_string(&area).unwrap();
+    assert_eq!(json, "\"5.20\"");
+    let back: Typed<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!((back.get(), back.text()), (5.2, "5.20"));
+
+    let xml = read_xml_file(HISTORY).unwrap()
+        .replacen("<tst:StemCount>311</tst:StemCount>", "<tst:StemCount>0311</tst:StemCount>", 1);
+    let data = ForestPropertyData::try_from_xml_str(&xml).unwrap();
+    let stand = data.stands().find(|s| s.id == "29727379").unwrap();
+    assert_eq!(stand.st_stand_basic_data.st_area.text(), "5.20");
+
+    let json = serde_json::to_string(&data).unwrap();
+    let data: ForestPropertyData = serde_json::from_str(&json).unwrap();
+    let written = String::from_utf8(data.to_xml(&XmlWriteOptions::default()).unwrap()).unwrap();
+    assert!(written.contains("<st:Area>5.20</st:Area>"));
+    assert!(written.contains("<tst:StemCount>0311</tst:StemCount>"));
+}
@@ -0,0 +1,64 @@
+use forestry_xml_parser::codes::TreeSpecies;
+use forestry_xml_parser::forest_property_data::ForestPropertyData;
+use forestry_xml_parser::values::Typed;
+
+mod common;
+
+use common::{approx, history_stand};
+
+#[test]
+fn stand_breakdown_uses_latest_non_forecast_data() {
+    let stand = history_stand("29727379");
+    assert_eq!(stand.current_tree_stand_data().unwrap().date.to_string(), "2020-01-01");
+
+    let breakdown = stand.volume_breakdown();
+    assert!(approx(breakdown.area_ha, 5.2));
+    let species: Vec<_> = breakdown.species.iter().map(|s| s.species.clone()).collect();
+    assert_eq!(species, [TreeSpecies::ScotsPine, TreeSpecies::NorwaySpruce, TreeSpecies::Deciduous]);
+    assert!(approx(breakdown.species[0].volume, 0.5 * 5.2));
+    assert!(approx(breakdown.volume(), 1.3 * 5.2));
+
+    let per_ha = breakdown.per_hectare();
+    assert!(approx(per_ha.species[1].volume, 0.3));
+    assert!(approx(per_ha.volume(), 1.3));
+}
+
+#[test]
+fn saw_log_volume_falls_back_to_percent() {
+    let mut stand = history_stand("29727379");
+    let date = &mut stand.ts_tree_stand_data.as_mut().unwrap().ts_tree_stand_data_date[1];
+    let stratum = &mut date.tst_tree_strata.as_mut().unwrap().tst_tree_stratum[0];
+    stratum.tst_saw_log_volume = None;
+    stratum.tst_saw_log_percent = Some(Typed::parse("40").unwrap());
+
+    let per_ha = stand.volume_breakdown().per_hectare();
+    assert!(approx(per_ha.species[0].saw_log_volume, 0.2));
+}
+
+#[test]
+fn levels_add_up() {
+    let data = ForestPropertyData::try_from_xml_file("orig_forestpropertydata.xml").unwrap();
+    let document = data.volume_breakdown();
+    assert!((document.area_ha - 546.88).abs() < 0.005);
+
+    let estate = data.real_estates().next().unwrap();
+    assert_eq!(estate.volume_breakdown(), document);
+
+    let parcels: Vec<_> = estate.re_parcels.re_parcel.iter().map(|parcel| parcel.volume_breakdown()).collect();
+    assert!(approx(parcels.iter().map(|p| p.volume()).sum(), document.volume()));
+    assert!(approx(parcels.iter().map(|p| p.saw_log_volume()).sum(), document.saw_log_volume()));
+
+    let stands: f64 = data.stands().map(|stand| stand.volume_breakdown().pulp_wood_volume()).sum();
+    assert!(approx(stands, document.pulp_wood_volume()));
+}
+
+#[test]
+fn stand_without_data_has_zero_volumes() {
+    let mut stand = history_stand("29727379");
+    stand.ts_tree_stand_data = None;
+
+    let breakdown = stand.volume_breakdown();
+    assert!(breakdown.species.is_empty());
+    let totals = [breakdown.volume(), breakdown.saw_log_volume(), breakdown.pulp_wood_volume()];
+    assert!(totals.iter().all(|total| *total == 0.0 && total.is_sign_positive()));
+}